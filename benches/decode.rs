@@ -0,0 +1,181 @@
+//! 输入事件解码耗时：旧的 JSON 字符串二次解析 vs structured clone 对象直接解出
+//!
+//! 需在 wasm 环境中运行：
+//! cargo bench --target wasm32-unknown-unknown --bench decode
+#![cfg(target_arch = "wasm32")]
+
+use bevy_in_web_worker::protocol::{HostToWorker, KeyboardEvent, MouseEvent};
+use serde::Deserialize;
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::{Criterion, wasm_bindgen_bench};
+
+/// 旧协议：`event` 是 index.js 中 stringifyEvent 序列化后的整个 DOM 事件
+#[derive(Deserialize)]
+struct LegacyMessage {
+    #[allow(dead_code)]
+    ty: String,
+    event: Option<String>,
+}
+
+/// stringifyEvent 对一个 mousemove 事件的输出
+const LEGACY_MOUSE_MOVE: &str = r#"{
+ "isTrusted": true,
+ "screenX": 512,
+ "screenY": 384,
+ "clientX": 412,
+ "clientY": 284,
+ "ctrlKey": false,
+ "shiftKey": false,
+ "altKey": false,
+ "metaKey": false,
+ "button": 0,
+ "buttons": 0,
+ "relatedTarget": null,
+ "pageX": 412,
+ "pageY": 284,
+ "x": 412,
+ "y": 284,
+ "offsetX": 204,
+ "offsetY": 132,
+ "movementX": 2,
+ "movementY": -1,
+ "fromElement": null,
+ "toElement": "Node",
+ "layerX": 204,
+ "layerY": 132,
+ "view": "Window",
+ "detail": 0,
+ "sourceCapabilities": null,
+ "which": 0,
+ "type": "mousemove",
+ "target": "Node",
+ "currentTarget": "Node",
+ "eventPhase": 2,
+ "bubbles": true,
+ "cancelable": true,
+ "defaultPrevented": false,
+ "composed": true,
+ "timeStamp": 10234.5,
+ "srcElement": "Node",
+ "returnValue": true,
+ "cancelBubble": false,
+ "NONE": 0,
+ "CAPTURING_PHASE": 1,
+ "AT_TARGET": 2,
+ "BUBBLING_PHASE": 3
+}"#;
+
+/// stringifyEvent 对一个 keydown 事件的输出
+const LEGACY_KEY_DOWN: &str = r#"{
+ "isTrusted": true,
+ "key": "a",
+ "code": "KeyA",
+ "location": 0,
+ "ctrlKey": false,
+ "shiftKey": false,
+ "altKey": false,
+ "metaKey": false,
+ "repeat": false,
+ "isComposing": false,
+ "charCode": 0,
+ "keyCode": 65,
+ "view": "Window",
+ "detail": 0,
+ "sourceCapabilities": null,
+ "which": 65,
+ "type": "keydown",
+ "target": "Node",
+ "currentTarget": "Node",
+ "eventPhase": 2,
+ "bubbles": true,
+ "cancelable": true,
+ "defaultPrevented": false,
+ "composed": true,
+ "timeStamp": 10234.5,
+ "srcElement": "Node",
+ "returnValue": true,
+ "cancelBubble": false,
+ "NONE": 0,
+ "CAPTURING_PHASE": 1,
+ "AT_TARGET": 2,
+ "BUBBLING_PHASE": 3
+}"#;
+
+/// mouse_move 消息，字段与 protocol::MouseEvent 一致
+const MOUSE_MOVE: &str = r#"{
+ "ty": "mouse_move",
+ "event": {
+  "altKey": false,
+  "button": 0,
+  "buttons": 0,
+  "clientX": 412,
+  "clientY": 284,
+  "ctrlKey": false,
+  "metaKey": false,
+  "offsetX": 204,
+  "offsetY": 132,
+  "pageX": 412,
+  "pageY": 284,
+  "screenX": 512,
+  "screenY": 384,
+  "shiftKey": false
+ }
+}"#;
+
+/// index.js 中 keyboardEventInit 的输出
+const KEY_DOWN: &str = r#"{
+ "ty": "key_down",
+ "event": {
+  "altKey": false,
+  "code": "KeyA",
+  "ctrlKey": false,
+  "isComposing": false,
+  "key": "a",
+  "location": 0,
+  "metaKey": false,
+  "shiftKey": false,
+  "repeat": false
+ }
+}"#;
+
+fn legacy_message(ty: &str, event: &str) -> JsValue {
+    let obj = js_sys::Object::new();
+    js_sys::Reflect::set(&obj, &"ty".into(), &ty.into()).unwrap();
+    js_sys::Reflect::set(&obj, &"event".into(), &event.into()).unwrap();
+    obj.into()
+}
+
+fn decode_legacy<T: for<'de> Deserialize<'de>>(val: &JsValue) -> T {
+    let msg: LegacyMessage = serde_wasm_bindgen::from_value(val.clone()).unwrap();
+    serde_json::from_str(&msg.event.unwrap()).unwrap()
+}
+
+fn decode(val: &JsValue) -> HostToWorker {
+    serde_wasm_bindgen::from_value(val.clone()).unwrap()
+}
+
+#[wasm_bindgen_bench]
+fn mouse_move(c: &mut Criterion) {
+    let legacy = legacy_message("mouse_move", LEGACY_MOUSE_MOVE);
+    c.bench_function("mouse_move/json_string", |b| {
+        b.iter(|| decode_legacy::<MouseEvent>(&legacy))
+    });
+
+    let structured = js_sys::JSON::parse(MOUSE_MOVE).unwrap();
+    c.bench_function("mouse_move/structured_clone", |b| {
+        b.iter(|| decode(&structured))
+    });
+}
+
+#[wasm_bindgen_bench]
+fn key_down(c: &mut Criterion) {
+    let legacy = legacy_message("key_down", LEGACY_KEY_DOWN);
+    c.bench_function("key_down/json_string", |b| {
+        b.iter(|| decode_legacy::<KeyboardEvent>(&legacy))
+    });
+
+    let structured = js_sys::JSON::parse(KEY_DOWN).unwrap();
+    c.bench_function("key_down/structured_clone", |b| {
+        b.iter(|| decode(&structured))
+    });
+}
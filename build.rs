@@ -71,7 +71,7 @@ fn parse(text: &str) -> Vec<Entry> {
     display
 }
 
-fn print_keys_codes(keys: &[Entry], codes: &[Entry], file: &mut File) -> std::io::Result<()> {
+fn print_keys(keys: &[Entry], file: &mut File) -> std::io::Result<()> {
     // https://github.com/rust-windowing/winit/blob/da6220060e7626c11332354cc26cd47e2937c200/winit-web/src/web_sys/event.rs#L257
     // https://github.com/bevyengine/bevy/blob/24729499ed63606d99969a78792b4246fa139dd4/crates/bevy_winit/src/converters.rs#L305
     // https://github.com/bevyengine/bevy/blob/24729499ed63606d99969a78792b4246fa139dd4/crates/bevy_winit/src/converters.rs#L634
@@ -110,37 +110,60 @@ fn print_keys_codes(keys: &[Entry], codes: &[Entry], file: &mut File) -> std::io
         }
     }
 
+    Ok(())
+}
+
+fn print_codes(codes: &[Entry], file: &mut File) -> std::io::Result<()> {
     for (key, _, _, alternatives, _) in codes {
-        if keys.iter().any(|(k, _, _, _, _)| k == key) {
+        if key == "Unidentified" {
             continue;
         }
         write!(file, "            \"{}\"", key)?;
         for alternative in alternatives {
             write!(file, " | \"{}\"", alternative)?;
         }
-        writeln!(file, " => Key::Character(SmolStr::new(\"{}\")),", key)?;
+        writeln!(file, " => KeyCode::{},", key_code_variant(key))?;
     }
     Ok(())
 }
 
-fn print_codes(codes: &[Entry], file: &mut File) -> std::io::Result<()> {
-    for (key, _, _, alternatives, _) in codes {
-        if key == "Unidentified" {
-            continue;
-        }
-        let mut key = key.to_string();
-        if key == "MetaLeft" || key == "MetaRight" || key == "Super" {
-            key = "Meta".to_string();
+fn print_w3c_keys(keys: &[Entry], file: &mut File) -> std::io::Result<()> {
+    for (key, ..) in keys {
+        if key == "Dead" {
+            writeln!(file, "            Key::Dead(_) => \"Dead\",")?;
+        } else if key != "Unidentified" {
+            writeln!(file, "            Key::{} => \"{}\",", key, key)?;
         }
-        write!(file, "            \"{}\"", key)?;
-        for alternative in alternatives {
-            write!(file, " | \"{}\"", alternative)?;
+    }
+    Ok(())
+}
+
+/// 别名（如 "OSLeft"）都转回规范名
+fn print_w3c_codes(codes: &[Entry], file: &mut File) -> std::io::Result<()> {
+    for (code, ..) in codes {
+        if code == "Unidentified" {
+            continue;
         }
-        writeln!(file, " => KeyCode::{},", key)?;
+        writeln!(
+            file,
+            "            KeyCode::{} => \"{}\",",
+            key_code_variant(code),
+            code
+        )?;
     }
     Ok(())
 }
 
+/// W3C code 与 bevy `KeyCode` 变体名不一致的情况
+fn key_code_variant(code: &str) -> &str {
+    match code {
+        "MetaLeft" => "SuperLeft",
+        "MetaRight" => "SuperRight",
+        "Super" => "Meta",
+        code => code,
+    }
+}
+
 fn add_comment_to(display: &mut [Entry], key: &str, comment: &str) {
     for (found_key, doc_comment, ..) in display.iter_mut() {
         if found_key == key {
@@ -150,11 +173,7 @@ fn add_comment_to(display: &mut [Entry], key: &str, comment: &str) {
     }
 }
 
-fn add_alias_for(
-    display: &mut [(String, String, bool, Vec<String>, Vec<String>)],
-    key: &str,
-    alias: &str,
-) {
+fn add_alias_for(display: &mut [Entry], key: &str, alias: &str) {
     for (found_key, _, _, _, aliases) in display.iter_mut() {
         if found_key == key {
             aliases.push(alias.to_string());
@@ -198,7 +217,7 @@ fn keys(text: &str) -> Vec<Entry> {
 
 type Entry = (String, String, bool, Vec<String>, Vec<String>);
 
-fn convert_key(keys: &[Entry], codes: &[Entry], mut file: &mut File) -> std::io::Result<()> {
+fn convert_key(keys: &[Entry], file: &mut File) -> std::io::Result<()> {
     write!(
         file,
         r#"
@@ -213,11 +232,34 @@ impl AsKey for &str {{
 "#
     )?;
 
-    print_keys_codes(&keys, codes, &mut file)?;
+    print_keys(keys, file)?;
     write!(
         file,
         r#"
-            _ => todo!(),
+            // 非命名键的 key 值是按键产生的文本
+            key if key.graphemes(true).count() == 1 => Key::Character(SmolStr::new(key)),
+            key => Key::Unidentified(NativeKey::Web(SmolStr::new(key))),
+        }}
+    }}
+}}
+
+/// [`AsKey`] 的逆转换，得到 `KeyboardEvent.key` 的值
+pub trait ToW3cKey {{
+    fn to_w3c_key(&self) -> &str;
+}}
+
+impl ToW3cKey for Key {{
+    fn to_w3c_key(&self) -> &str {{
+        match self {{
+            Key::Character(text) => text,
+            Key::Space => " ",
+            Key::Unidentified(NativeKey::Web(key)) => key,
+"#,
+    )?;
+    print_w3c_keys(keys, file)?;
+    write!(
+        file,
+        r#"            _ => "Unidentified",
         }}
     }}
 }}"#,
@@ -281,13 +323,14 @@ fn header(file: &mut File) -> std::io::Result<()> {
 #![allow(deprecated)]
 
 use bevy_input::keyboard::{{NativeKey, Key, NativeKeyCode, KeyCode}};
-use smol_str::SmolStr;"#
+use smol_str::SmolStr;
+use unicode_segmentation::UnicodeSegmentation;"#
     )?;
 
     Ok(())
 }
 
-fn convert_code(codes: &[Entry], mut file: &mut File) -> std::io::Result<()> {
+fn convert_code(codes: &[Entry], file: &mut File) -> std::io::Result<()> {
     write!(
         file,
         r#" 
@@ -300,32 +343,197 @@ impl AsKeyCode for &str {{
         match *self {{
 "#
     )?;
-    print_codes(&codes, &mut file)?;
+    print_codes(codes, file)?;
     write!(
         file,
         r#"
             _ => KeyCode::Unidentified(NativeKeyCode::Unidentified),
         }}
     }}
+}}
+
+/// [`AsKeyCode`] 的逆转换，得到 `KeyboardEvent.code` 的值
+pub trait ToW3cCode {{
+    fn to_w3c_code(&self) -> &'static str;
+}}
+
+impl ToW3cCode for KeyCode {{
+    fn to_w3c_code(&self) -> &'static str {{
+        match self {{
+"#
+    )?;
+    print_w3c_codes(codes, file)?;
+    write!(
+        file,
+        r#"            KeyCode::Unidentified(_) => "Unidentified",
+        }}
+    }}
 }}"#
     )?;
 
     Ok(())
 }
 
+fn tests(keys: &[Entry], codes: &[Entry], file: &mut File) -> std::io::Result<()> {
+    write!(
+        file,
+        r#"
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    const KEYS: &[&str] = &[
+"#
+    )?;
+    for (key, ..) in keys {
+        writeln!(file, "        \"{}\",", key)?;
+    }
+    write!(
+        file,
+        r#"    ];
+
+    /// (code, KeyCode 变体名, 别名)
+    const CODES: &[(&str, &str, &[&str])] = &[
+"#
+    )?;
+    for (code, _, _, alternatives, _) in codes {
+        if code == "Unidentified" {
+            continue;
+        }
+        let alternatives: Vec<_> = alternatives.iter().map(|a| format!("\"{}\"", a)).collect();
+        writeln!(
+            file,
+            "        (\"{}\", \"{}\", &[{}]),",
+            code,
+            key_code_variant(code),
+            alternatives.join(", ")
+        )?;
+    }
+    write!(
+        file,
+        r#"    ];
+
+    #[test]
+    fn keys_round_trip() {{
+        for &key in KEYS {{
+            match key.as_key() {{
+                Key::Dead(None) => assert_eq!(key, "Dead"),
+                Key::Unidentified(NativeKey::Web(name)) => assert_eq!(key, name),
+                converted => assert_eq!(format!("{{converted:?}}"), key),
+            }}
+            assert_eq!(key.as_key().to_w3c_key(), key);
+        }}
+    }}
+
+    #[test]
+    fn codes_round_trip() {{
+        for &(code, variant, alternatives) in CODES {{
+            let converted = code.as_key_code();
+            assert_eq!(format!("{{converted:?}}"), variant, "{{code}}");
+            assert_eq!(converted.to_w3c_code(), code);
+            for alternative in alternatives {{
+                assert_eq!(alternative.as_key_code(), converted, "{{alternative}}");
+                assert_eq!(alternative.as_key_code().to_w3c_code(), code);
+            }}
+        }}
+    }}
+
+    #[test]
+    fn characters() {{
+        for text in ["a", "A", "1", " ", "é", "e\u{{301}}", "€", "ß", "あ", "👍🏽", "👨‍👩‍👧"] {{
+            assert_eq!(text.as_key(), Key::Character(SmolStr::new(text)));
+            assert_eq!(text.as_key().to_w3c_key(), text);
+        }}
+        assert_eq!(Key::Space.to_w3c_key(), " ");
+    }}
+
+    #[test]
+    fn unknown_names() {{
+        for name in ["", "KeyA", "NotAKey", "ab"] {{
+            assert_eq!(
+                name.as_key(),
+                Key::Unidentified(NativeKey::Web(SmolStr::new(name)))
+            );
+            assert_eq!(name.as_key().to_w3c_key(), name);
+        }}
+        assert_eq!(Key::Unidentified(NativeKey::Unidentified).to_w3c_key(), "Unidentified");
+        assert_eq!(
+            "NotACode".as_key_code(),
+            KeyCode::Unidentified(NativeKeyCode::Unidentified)
+        );
+        assert_eq!(
+            KeyCode::Unidentified(NativeKeyCode::Xkb(0)).to_w3c_code(),
+            "Unidentified"
+        );
+    }}
+}}
+"#
+    )?;
+
+    Ok(())
+}
+
 // https://github.com/rust-windowing/winit/blob/master/winit-web/src/web_sys/event.rs
 // https://github.com/rust-windowing/keyboard-types/blob/main/convert.py
 // https://github.com/rust-windowing/keyboard-types/blob/main/src/code.rs#L716
 // https://github.com/rust-windowing/keyboard-types/blob/main/src/named_key.rs
 // Use keyboard-types' parsing (it is based on the W3C standard).
+//
+// 默认只根据 spec/ 下的表格生成到 OUT_DIR，供测试比对 src/keyboard.rs 是否最新。
+// 这些表格由现有的 src/keyboard.rs 反推而来，并非规范原文，见其文件头。
+// 设置 REGENERATE_KEYBOARD=1 时覆盖 src/keyboard.rs，
+// 同时开启 `fetch-spec` feature 时先从 w3c.github.io 下载规范原文覆盖 spec/ 下的文件。
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let mut file = File::create("src/keyboard.rs")?;
-    let key = reqwest::blocking::get("https://w3c.github.io/uievents-key/")?.text()?;
-    let keys = keys(&key);
-    let code = reqwest::blocking::get("https://w3c.github.io/uievents-code/")?.text()?;
-    let codes = codes(&code);
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={KEY_SPEC}");
+    println!("cargo:rerun-if-changed={CODE_SPEC}");
+    println!("cargo:rerun-if-env-changed=REGENERATE_KEYBOARD");
+
+    let regenerate = std::env::var_os("REGENERATE_KEYBOARD").is_some_and(|v| v == "1");
+    #[cfg(feature = "fetch-spec")]
+    if regenerate {
+        fetch_spec()?;
+    }
+
+    let out = std::path::Path::new(&std::env::var("OUT_DIR")?).join("keyboard.rs");
+    let mut file = File::create(&out)?;
+    let keys = keys(&std::fs::read_to_string(KEY_SPEC)?);
+    let codes = codes(&std::fs::read_to_string(CODE_SPEC)?);
     header(&mut file)?;
-    convert_key(&keys, &codes, &mut file)?;
+    convert_key(&keys, &mut file)?;
     convert_code(&codes, &mut file)?;
+    tests(&keys, &codes, &mut file)?;
+
+    if regenerate {
+        std::fs::copy(&out, "src/keyboard.rs")?;
+    }
+    Ok(())
+}
+
+const KEY_SPEC: &str = "spec/uievents-key.html";
+const CODE_SPEC: &str = "spec/uievents-code.html";
+
+#[cfg(feature = "fetch-spec")]
+fn fetch_spec() -> Result<(), Box<dyn std::error::Error>> {
+    fetch(KEY_SPEC, "https://w3c.github.io/uievents-key/")?;
+    fetch(CODE_SPEC, "https://w3c.github.io/uievents-code/")
+}
+
+/// 原文之前加一行注释记录来源与服务器返回的日期，便于审查 spec/ 下的文件确为规范原文
+#[cfg(feature = "fetch-spec")]
+fn fetch(path: &str, url: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let response = reqwest::blocking::get(url)?.error_for_status()?;
+    let date = response
+        .headers()
+        .get(reqwest::header::DATE)
+        .and_then(|date| date.to_str().ok())
+        .unwrap_or("unknown")
+        .to_string();
+    let body = response.text()?;
+    std::fs::write(
+        path,
+        format!("<!-- Source: {url} Retrieved: {date} -->\n{body}"),
+    )?;
     Ok(())
 }
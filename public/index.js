@@ -1,21 +1,118 @@
+// 须与 src/protocol.rs 中的 PROTOCOL_VERSION 保持一致
+const PROTOCOL_VERSION = 2;
+
 const worker = new Worker("./worker.js", { type: "module" });
 
+// 最近一次在画布上按下的指针，worker 请求 pointer capture 时作用于它
+let activePointerId = null;
+
+// worker 启用输入法时，文本输入交给画布上这个隐藏的 <input>
+let imeEnabled = false;
+
 worker.onmessage = async (event) => {
   let data = event.data;
   switch (data.ty) {
     case "ready":
+      if (data.version !== PROTOCOL_VERSION) {
+        console.error(
+          `protocol version mismatch: host ${PROTOCOL_VERSION}, worker ${data.version}`
+        );
+        break;
+      }
       addEventObserver();
+      observeCanvasSize("worker-thread-container");
+      pollGamepads();
       let loading = document.getElementById("loading");
       loading.style.display = "none";
       break;
     case "pick":
       document.getElementById("hovers").innerText = data.list;
       break;
+    case "pointer_capture":
+      setPointerCapture(data.capture);
+      break;
+    case "cursor":
+      applyCursor(data);
+      break;
+    case "ime":
+      applyIme(data);
+      break;
+    case "clipboard_write":
+      replyClipboard(data.id, () => navigator.clipboard.writeText(data.text));
+      break;
+    case "clipboard_read":
+      replyClipboard(data.id, () => navigator.clipboard.readText());
+      break;
+    case "input_stats":
+      document.getElementById("input-stats").innerText =
+        `${data.events} events, ` +
+        `queue p50 ${data.queueDelay.p50.toFixed(1)} / p99 ${data.queueDelay.p99.toFixed(1)} ms, ` +
+        `latency p50 ${data.latency.p50.toFixed(1)} / p99 ${data.latency.p99.toFixed(1)} ms, ` +
+        `${data.merged} of ${data.received} messages coalesced`;
+      break;
+    case "error":
+      console.error("[worker]", data.message);
+      break;
     default:
       break;
   }
 };
 
+function setPointerCapture(capture) {
+  let container = document.getElementById("worker-thread-container");
+  if (activePointerId === null) {
+    return;
+  }
+  // 请求到达前指针可能已经抬起
+  try {
+    if (capture) {
+      container.setPointerCapture(activePointerId);
+    } else if (container.hasPointerCapture(activePointerId)) {
+      container.releasePointerCapture(activePointerId);
+    }
+  } catch (e) {}
+}
+
+function applyCursor({ grabMode, visible, icon }) {
+  let container = document.getElementById("worker-thread-container");
+  container.style.cursor = visible ? icon : "none";
+  // 浏览器不支持把光标限制在元素内，confined 按 none 处理
+  if (grabMode === "locked") {
+    if (document.pointerLockElement !== container) {
+      // 新版浏览器返回 Promise，失败时另有 pointerlockerror
+      Promise.resolve(container.requestPointerLock()).catch(() => {});
+    }
+  } else if (document.pointerLockElement === container) {
+    document.exitPointerLock();
+  }
+}
+
+// 剪贴板须在页面获得焦点（读取时还需用户授权）时访问，失败原因回传给 worker；
+// 非安全上下文中没有 navigator.clipboard
+function replyClipboard(id, request) {
+  Promise.resolve().then(request).then(
+    (text) => worker.postMessage({ ty: "clipboard", event: { id, text } }),
+    (e) => worker.postMessage({ ty: "clipboard", event: { id, error: `${e.name}: ${e.message}` } })
+  );
+}
+
+function applyIme({ enabled, x, y }) {
+  let container = document.getElementById("worker-thread-container");
+  let input = document.getElementById("ime-input");
+  // 候选框跟随输入元素的位置
+  input.style.left = `${x}px`;
+  input.style.top = `${y}px`;
+  if (enabled === imeEnabled) {
+    return;
+  }
+  imeEnabled = enabled;
+  if (enabled && document.activeElement === container) {
+    input.focus();
+  } else if (!enabled && document.activeElement === input) {
+    container.focus();
+  }
+}
+
 function resizeCanvas(containerID) {
   let elem = document.getElementById(containerID);
   let canvas = elem.children[0];
@@ -28,31 +125,118 @@ function resizeCanvas(containerID) {
   canvas.style.maxHeight = elem.clientHeight + "px";
 }
 
-function stringifyEvent(e) {
-  const obj = {};
-  for (let k in e) {
-    obj[k] = e[k];
-  }
-  return JSON.stringify(
-    obj,
-    (k, v) => {
-      if (v instanceof Node) return "Node";
-      if (v instanceof Window) return "Window";
-      return v;
-    },
-    " "
-  );
+// 画布控制权转交给 worker 后，像素尺寸只能由 worker 修改
+function observeCanvasSize(containerID) {
+  let elem = document.getElementById(containerID);
+  let canvas = elem.children[0];
+  let sendResize = () => {
+    canvas.style.width = elem.clientWidth + "px";
+    canvas.style.maxWidth = elem.clientWidth + "px";
+    canvas.style.height = elem.clientHeight + "px";
+    canvas.style.maxHeight = elem.clientHeight + "px";
+    worker.postMessage({
+      ty: "resize",
+      event: {
+        width: canvas.clientWidth,
+        height: canvas.clientHeight,
+        devicePixelRatio: window.devicePixelRatio,
+      },
+    });
+  };
+  new ResizeObserver(sendResize).observe(elem);
+
+  // 窗口在不同 DPR 的屏幕间移动或缩放页面时，devicePixelRatio 会变化
+  let watchPixelRatio = () => {
+    matchMedia(`(resolution: ${window.devicePixelRatio}dppx)`).addEventListener(
+      "change",
+      () => {
+        sendResize();
+        watchPixelRatio();
+      },
+      { once: true }
+    );
+  };
+  watchPixelRatio();
+}
+
+// worker 的 performance.now() 基于另一个 timeOrigin，统一换算为绝对时间
+function timeStamp(e) {
+  return performance.timeOrigin + e.timeStamp;
+}
+
+// DOM 事件无法 structured clone，只挑出 worker 需要的字段
+function pointerEventInit(e) {
+  return {
+    pointerId: e.pointerId,
+    pointerType: e.pointerType,
+    isPrimary: e.isPrimary,
+    altKey: e.altKey,
+    button: e.button,
+    buttons: e.buttons,
+    ctrlKey: e.ctrlKey,
+    metaKey: e.metaKey,
+    movementX: e.movementX,
+    movementY: e.movementY,
+    offsetX: e.offsetX,
+    offsetY: e.offsetY,
+    shiftKey: e.shiftKey,
+    pressure: e.pressure,
+    tangentialPressure: e.tangentialPressure,
+    tiltX: e.tiltX,
+    tiltY: e.tiltY,
+    twist: e.twist,
+    width: e.width,
+    height: e.height,
+    timeStamp: timeStamp(e),
+    coalesced: [],
+  };
+}
+
+function keyboardEventInit(e) {
+  return {
+    altKey: e.altKey,
+    code: e.code,
+    ctrlKey: e.ctrlKey,
+    isComposing: e.isComposing,
+    key: e.key,
+    location: e.location,
+    metaKey: e.metaKey,
+    shiftKey: e.shiftKey,
+    repeat: e.repeat,
+    timeStamp: timeStamp(e),
+  };
+}
+
+function compositionEventInit(e) {
+  return {
+    data: e.data ?? "",
+    timeStamp: timeStamp(e),
+  };
+}
+
+function wheelEventInit(e) {
+  return {
+    deltaX: e.deltaX,
+    deltaY: e.deltaY,
+    deltaZ: e.deltaZ,
+    deltaMode: e.deltaMode,
+    timeStamp: timeStamp(e),
+  };
 }
 
 // https://macroquad.rs/examples/
 function addEventObserver() {
   let container = document.getElementById("worker-thread-container");
-  container.onmousemove = function (event) {
+  container.onpointermove = function (event) {
     event.preventDefault();
     window.blockMS(window.mousemoveBlockTime);
+    let init = pointerEventInit(event);
+    if (event.getCoalescedEvents) {
+      init.coalesced = event.getCoalescedEvents().map(pointerEventInit);
+    }
     worker.postMessage({
-      ty: "mouse_move",
-      event: stringifyEvent(event),
+      ty: "pointer_move",
+      event: init,
     });
   };
 
@@ -60,60 +244,172 @@ function addEventObserver() {
     event.preventDefault();
     worker.postMessage({
       ty: "wheel",
-      event: stringifyEvent(event),
+      event: wheelEventInit(event),
     });
   };
 
+  let imeInput = document.getElementById("ime-input");
+
+  // 输入法的按键须保留默认行为，否则无法组字
   container.onkeyup = function (event) {
     if (!(event.key === "r" && event.metakey)) {
-      event.preventDefault();
+      if (event.target !== imeInput) {
+        event.preventDefault();
+      }
       worker.postMessage({
         ty: "key_up",
-        event: stringifyEvent(event),
+        event: keyboardEventInit(event),
       });
     }
   };
 
   container.onkeydown = function (event) {
     if (!(event.key === "r" && event.metakey)) {
-      event.preventDefault();
+      if (event.target !== imeInput) {
+        event.preventDefault();
+      }
       worker.postMessage({
         ty: "key_down",
-        event: stringifyEvent(event),
+        event: keyboardEventInit(event),
       });
     }
   };
 
-  container.onmouseup = function (event) {
+  imeInput.addEventListener("compositionstart", (event) => {
+    worker.postMessage({
+      ty: "composition_start",
+      event: compositionEventInit(event),
+    });
+  });
+  imeInput.addEventListener("compositionupdate", (event) => {
+    worker.postMessage({
+      ty: "composition_update",
+      event: compositionEventInit(event),
+    });
+  });
+  imeInput.addEventListener("compositionend", (event) => {
     worker.postMessage({
-      ty: "mouse_up",
-      event: stringifyEvent(event),
+      ty: "composition_end",
+      event: compositionEventInit(event),
+    });
+    imeInput.value = "";
+  });
+  // 非组字输入的文本已随 keydown 发出
+  imeInput.addEventListener("input", (event) => {
+    if (!event.isComposing) {
+      imeInput.value = "";
+    }
+  });
+
+  container.onpointerup = function (event) {
+    worker.postMessage({
+      ty: "pointer_up",
+      event: pointerEventInit(event),
     });
   };
 
-  container.onmousedown = function (event) {
+  container.onpointerdown = function (event) {
+    activePointerId = event.pointerId;
     worker.postMessage({
-      ty: "mouse_down",
-      event: stringifyEvent(event),
+      ty: "pointer_down",
+      event: pointerEventInit(event),
     });
   };
 
+  container.onpointercancel = function (event) {
+    worker.postMessage({
+      ty: "pointer_cancel",
+      event: pointerEventInit(event),
+    });
+  };
+
+  container.onpointerenter = function (event) {
+    worker.postMessage({
+      ty: "pointer_enter",
+      event: pointerEventInit(event),
+    });
+  };
+
+  container.onpointerleave = function (event) {
+    worker.postMessage({
+      ty: "pointer_leave",
+      event: pointerEventInit(event),
+    });
+  };
+
+  // 锁定状态以浏览器为准回报给 worker
+  document.addEventListener("pointerlockchange", () => {
+    worker.postMessage({
+      ty: "pointer_lock",
+      event: document.pointerLockElement === container,
+    });
+  });
+  document.addEventListener("pointerlockerror", () => {
+    worker.postMessage({ ty: "pointer_lock", event: false });
+  });
+
   container.onclick = function (event) {
     event.preventDefault();
-    container.focus();
+    (imeEnabled ? imeInput : container).focus();
   };
 
+  // 焦点在容器与输入元素之间移动时，对 worker 而言仍是同一个窗口
   container.onfocus = function (event) {
     container.style.border = "2px solid red";
+    if (event.relatedTarget !== imeInput) {
+      worker.postMessage({ ty: "focus", event: true });
+    }
+  };
+  imeInput.onfocus = function (event) {
+    container.style.border = "2px solid red";
+    if (event.relatedTarget !== container) {
+      worker.postMessage({ ty: "focus", event: true });
+    }
   };
 
+  // 失去焦点后收不到 keyup / mouseup，由 worker 松开所有按住的键
   container.onblur = function (event) {
     container.style.border = "2px solid black";
+    if (event.relatedTarget !== imeInput) {
+      worker.postMessage({ ty: "focus", event: false });
+    }
+  };
+  imeInput.onblur = function (event) {
+    container.style.border = "2px solid black";
+    if (event.relatedTarget !== container) {
+      worker.postMessage({ ty: "focus", event: false });
+    }
+  };
+}
+
+// worker 中没有 navigator.getGamepads()，由主线程每帧轮询，有变化时才发送
+function pollGamepads() {
+  if (!navigator.getGamepads) {
+    return;
+  }
+  let last = "[]";
+  let poll = () => {
+    let pads = Array.from(navigator.getGamepads())
+      .filter((pad) => pad && pad.connected)
+      .map((pad) => ({
+        index: pad.index,
+        id: pad.id,
+        mapping: pad.mapping,
+        buttons: pad.buttons.map((button) => button.value),
+        axes: Array.from(pad.axes),
+      }));
+    let json = JSON.stringify(pads);
+    if (json !== last) {
+      last = json;
+      worker.postMessage({ ty: "gamepads", event: pads });
+    }
+    requestAnimationFrame(poll);
   };
+  poll();
 }
 
 window.blockWorkerRender = (dt) => {
-  worker.postMessage({ ty: "blockRender", blockTime: dt });
+  worker.postMessage({ ty: "block_render", event: dt });
 };
 
 if ("navigator" in window && "gpu" in navigator) {
@@ -127,6 +423,7 @@ if ("navigator" in window && "gpu" in navigator) {
       worker.postMessage(
         {
           ty: "start",
+          version: PROTOCOL_VERSION,
           canvas: offscreenCanvas,
           devicePixelRatio: window.devicePixelRatio,
         },
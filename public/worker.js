@@ -10,10 +10,14 @@ export function block_from_worker() {
   while (performance.now() - start < renderBlockTime) {}
 }
 
+export function set_render_block_time(ms) {
+  renderBlockTime = ms;
+}
+
 onmessage = async (ev) => {
   let data = ev.data;
   if (data.ty === "start") {
     await init();
-    await init_bevy_app(data.canvas, data.devicePixelRatio);
+    await init_bevy_app(data.canvas, data.devicePixelRatio, data.version);
   }
 };
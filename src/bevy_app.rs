@@ -1,4 +1,11 @@
 use crate::WorkerApp;
+use crate::bridge::HostBridge;
+use crate::click::ClickPlugin;
+use crate::clipboard::{Clipboard, ClipboardPlugin, ClipboardRequest, ClipboardResponse};
+use crate::cursor::CursorPlugin;
+use crate::gesture::GesturePlugin;
+use crate::ime::ImePlugin;
+use crate::picking::{VolumePickingPlugin, VolumePickingSettings};
 use crate::ray_pick::RayPickPlugin;
 use crate::{OffscreenCanvas, OffscreenCanvasPlugin};
 use bevy::{
@@ -9,10 +16,15 @@ use bevy::{
     render::render_resource::{Extent3d, TextureDimension, TextureFormat},
 };
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::f32::consts::PI;
 use std::ops::Deref;
 
-pub(crate) fn init_app(canvas: web_sys::OffscreenCanvas, scale_factor: f32) -> WorkerApp {
+pub(crate) fn init_app(
+    canvas: web_sys::OffscreenCanvas,
+    scale_factor: f32,
+    host: impl HostBridge,
+) -> WorkerApp {
     let canvas = OffscreenCanvas::new(canvas, scale_factor);
     let mut app = App::new();
     app.add_plugins((
@@ -25,15 +37,25 @@ pub(crate) fn init_app(canvas: web_sys::OffscreenCanvas, scale_factor: f32) -> W
                 }),
                 ..default()
             }),
+        GesturePlugin,
+        ClickPlugin,
         RayPickPlugin,
+        VolumePickingPlugin,
+        CursorPlugin,
+        ImePlugin,
+        ClipboardPlugin,
     ))
+    // 胶囊、圆环等旋转后的包围盒远大于其外形，按三角形精确拾取
+    .insert_resource(VolumePickingSettings {
+        mesh_narrow_phase: true,
+    })
     .add_systems(Startup, setup)
-    .add_systems(Update, (rotate, update_aabbes))
+    .add_systems(Update, (rotate, update_aabbes, copy_paste))
     .add_systems(PostUpdate, (render_hovered_shapes, render_selected_shapes))
     .add_plugins(OffscreenCanvasPlugin)
     .insert_resource(canvas);
 
-    WorkerApp::new(app, scale_factor)
+    WorkerApp::new(app, host)
 }
 
 /// A marker component for our shapes so we can query them separately from the ground plane
@@ -45,7 +67,8 @@ enum Shape {
 #[derive(Component)]
 pub(crate) struct Hovered {}
 
-#[derive(Component)]
+/// 可拾取的对象；派生 `Clone` 使粘贴出的副本同样可拾取
+#[derive(Component, Clone)]
 pub(crate) struct Target {}
 
 #[derive(Component)]
@@ -79,11 +102,11 @@ fn setup(
         meshes.add(Cuboid::default()),
         meshes.add(Sphere::default().mesh().ico(5).unwrap()),
     ];
-    // 包围盒形状
+    // 包围盒形状，须包住对应的网格
     let shapes = [
         Shape::Box(Cuboid::from_size(Vec3::splat(1.1))),
         Shape::Box(Cuboid::from_size(Vec3::new(1., 2., 1.))),
-        Shape::Box(Cuboid::from_size(Vec3::new(1.75, 0.52, 1.75))),
+        Shape::Box(Cuboid::from_size(Vec3::new(3., 1., 3.))),
         Shape::Box(Cuboid::default()),
         Shape::Box(Cuboid::from_size(Vec3::new(1., 2., 1.))),
         Shape::Box(Cuboid::default()),
@@ -147,6 +170,7 @@ fn rotate(mut q: Query<&mut Transform, With<Shape>>, time: Res<Time>) {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn render_hovered_shapes(
     mut gizmos: Gizmos,
     q: Query<(&Shape, &Transform), (With<Hovered>, Without<Selected>)>,
@@ -180,6 +204,80 @@ fn render_selected_shapes(mut gizmos: Gizmos, q: Query<(&Shape, &Transform), Wit
     }
 }
 
+/// 剪贴板中的选中对象
+#[derive(Serialize, Deserialize)]
+struct CopiedShape {
+    entity: u64,
+    translation: [f32; 3],
+    rotation: [f32; 4],
+}
+
+/// 粘贴的副本相对原对象的偏移
+const PASTE_OFFSET: Vec3 = Vec3::new(0.5, -0.5, 0.5);
+
+/// Ctrl/Cmd + C 复制选中的对象，Ctrl/Cmd + V 在原对象旁粘贴副本并选中
+fn copy_paste(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut clipboard: ResMut<Clipboard>,
+    mut responses: MessageReader<ClipboardResponse>,
+    mut paste: Local<Option<ClipboardRequest>>,
+    selected: Query<(Entity, &Transform), With<Selected>>,
+    targets: Query<(), With<Target>>,
+) {
+    let command = keys.any_pressed([
+        KeyCode::ControlLeft,
+        KeyCode::ControlRight,
+        KeyCode::SuperLeft,
+        KeyCode::SuperRight,
+    ]);
+    if command && keys.just_pressed(KeyCode::KeyC) && !selected.is_empty() {
+        let shapes: Vec<_> = selected
+            .iter()
+            .map(|(entity, transform)| CopiedShape {
+                entity: entity.to_bits(),
+                translation: transform.translation.to_array(),
+                rotation: transform.rotation.to_array(),
+            })
+            .collect();
+        if let Err(e) = clipboard.copy_json(&shapes) {
+            warn!("[worker] copy failed: {e}");
+        }
+    }
+    if command && keys.just_pressed(KeyCode::KeyV) {
+        *paste = Some(clipboard.paste());
+    }
+
+    for response in responses.read() {
+        if Some(response.request) != *paste {
+            continue;
+        }
+        *paste = None;
+        // 剪贴板中可能是别处复制的文本
+        let Some(shapes) = response.json::<Vec<CopiedShape>>() else {
+            continue;
+        };
+        for (entity, _) in selected.iter() {
+            commands.entity(entity).remove::<Selected>();
+        }
+        for shape in shapes {
+            let Some(source) = Entity::try_from_bits(shape.entity).filter(|e| targets.contains(*e))
+            else {
+                continue;
+            };
+            let transform = Transform {
+                translation: Vec3::from_array(shape.translation) + PASTE_OFFSET,
+                rotation: Quat::from_array(shape.rotation),
+                ..default()
+            };
+            commands
+                .entity(source)
+                .clone_and_spawn()
+                .insert((transform, Selected {}));
+        }
+    }
+}
+
 /// Creates a colorful test pattern
 fn uv_debug_texture() -> Image {
     const TEXTURE_SIZE: usize = 8;
@@ -211,7 +309,7 @@ fn uv_debug_texture() -> Image {
 
 /// entity 的 aabb
 #[derive(Component, Debug)]
-pub struct CurrentVolume(Aabb3d);
+pub struct CurrentVolume(pub(crate) Aabb3d);
 
 impl Deref for CurrentVolume {
     type Target = Aabb3d;
@@ -222,6 +320,7 @@ impl Deref for CurrentVolume {
 }
 
 // 更新 aabb
+#[allow(clippy::type_complexity)]
 fn update_aabbes(
     mut commands: Commands,
     mut config_store: ResMut<GizmoConfigStore>,
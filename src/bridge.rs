@@ -0,0 +1,160 @@
+use crate::protocol::{HostToWorker, WorkerToHost};
+use bevy::prelude::*;
+use std::{
+    collections::VecDeque,
+    ops::Deref,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+/// worker 与宿主（主线程）之间的通信桥
+///
+/// 浏览器中由 `web_ffi::WorkerBridge` 实现，原生环境下用 [`MemoryBridge`]
+/// 以脚本化的消息流驱动 `WorkerApp`
+pub trait HostBridge: Send + Sync + 'static {
+    /// 向宿主发送消息
+    fn post(&self, msg: WorkerToHost);
+
+    /// 取出一条宿主发来的消息，没有时返回 `None`
+    fn recv(&self) -> Option<HostToWorker>;
+
+    /// 请求在下一帧调用 `WorkerApp::frame`
+    fn schedule_frame(&self);
+
+    /// 当前时间（毫秒），与宿主消息的 `time_stamp` 使用同一基准
+    fn now(&self) -> f64;
+}
+
+/// 供 system 使用的宿主通信桥
+#[derive(Resource, Clone)]
+pub struct Host(Arc<dyn HostBridge>);
+
+impl Host {
+    pub fn new(bridge: impl HostBridge) -> Self {
+        Self(Arc::new(bridge))
+    }
+}
+
+impl Deref for Host {
+    type Target = dyn HostBridge;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+/// 内存中的通信桥，clone 出的句柄共享同一组队列
+#[derive(Default, Clone)]
+pub struct MemoryBridge {
+    inner: Arc<MemoryBridgeInner>,
+}
+
+#[derive(Default)]
+struct MemoryBridgeInner {
+    incoming: Mutex<VecDeque<HostToWorker>>,
+    outgoing: Mutex<Vec<WorkerToHost>>,
+    frames: AtomicUsize,
+    now: Mutex<f64>,
+}
+
+impl MemoryBridge {
+    /// 模拟宿主发送一条消息
+    pub fn send(&self, msg: HostToWorker) {
+        self.inner.incoming.lock().unwrap().push_back(msg);
+    }
+
+    /// 取出 worker 已发送的全部消息
+    pub fn take_posted(&self) -> Vec<WorkerToHost> {
+        std::mem::take(&mut *self.inner.outgoing.lock().unwrap())
+    }
+
+    /// 累计请求过的帧数
+    pub fn frames_requested(&self) -> usize {
+        self.inner.frames.load(Ordering::Relaxed)
+    }
+
+    /// 设置 [`HostBridge::now`] 返回的时间
+    pub fn set_now(&self, ms: f64) {
+        *self.inner.now.lock().unwrap() = ms;
+    }
+}
+
+impl HostBridge for MemoryBridge {
+    fn post(&self, msg: WorkerToHost) {
+        self.inner.outgoing.lock().unwrap().push(msg);
+    }
+
+    fn recv(&self) -> Option<HostToWorker> {
+        self.inner.incoming.lock().unwrap().pop_front()
+    }
+
+    fn schedule_frame(&self) {
+        self.inner.frames.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn now(&self) -> f64 {
+        *self.inner.now.lock().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        WorkerApp,
+        protocol::{MouseEvent, PROTOCOL_VERSION},
+    };
+    use bevy::{input::InputPlugin, window::WindowPlugin};
+
+    fn worker_app(bridge: &MemoryBridge) -> WorkerApp {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, WindowPlugin::default()));
+        WorkerApp::new(app, bridge.clone())
+    }
+
+    #[test]
+    fn posts_ready_once_plugins_finish() {
+        let bridge = MemoryBridge::default();
+        let mut app = worker_app(&bridge);
+
+        app.frame();
+        assert_ne!(app.window, Entity::PLACEHOLDER);
+        assert_eq!(
+            bridge.take_posted(),
+            vec![WorkerToHost::Ready {
+                version: PROTOCOL_VERSION
+            }]
+        );
+        assert_eq!(bridge.frames_requested(), 1);
+
+        app.frame();
+        assert!(bridge.take_posted().is_empty());
+        assert_eq!(bridge.frames_requested(), 2);
+    }
+
+    #[test]
+    fn drains_scripted_messages_each_frame() {
+        let bridge = MemoryBridge::default();
+        let mut app = worker_app(&bridge);
+        app.frame();
+
+        bridge.send(HostToWorker::MouseDown(MouseEvent {
+            button: 2,
+            ..default()
+        }));
+        app.frame();
+        let buttons = app.world().resource::<ButtonInput<MouseButton>>();
+        assert!(buttons.just_pressed(MouseButton::Right));
+
+        bridge.send(HostToWorker::MouseUp(MouseEvent {
+            button: 2,
+            ..default()
+        }));
+        app.frame();
+        let buttons = app.world().resource::<ButtonInput<MouseButton>>();
+        assert!(buttons.just_released(MouseButton::Right));
+        assert!(bridge.recv().is_none());
+    }
+}
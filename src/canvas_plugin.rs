@@ -27,6 +27,18 @@ impl OffscreenCanvas {
     pub fn physical_resolution(&self) -> (u32, u32) {
         (self.inner.width(), self.inner.height())
     }
+
+    /// 设置画布的像素尺寸，尺寸不变时不做任何事（重设尺寸会清空画布）
+    pub fn set_physical_resolution(&self, width: u32, height: u32) {
+        // 0 尺寸无法配置 surface
+        let (width, height) = (width.max(1), height.max(1));
+        if self.inner.width() != width {
+            self.inner.set_width(width);
+        }
+        if self.inner.height() != height {
+            self.inner.set_height(height);
+        }
+    }
 }
 
 impl HasWindowHandle for OffscreenCanvas {
@@ -77,21 +89,20 @@ pub fn spawn_window(
     canvas: ResMut<OffscreenCanvas>,
 ) {
     // entity -> window + canvas (raw handler wrapper / window wrapper)
-    for (entity, mut window) in q.iter_mut() {
+    if let Some((entity, mut window)) = q.iter_mut().next() {
         let app_view = WindowWrapper::new(canvas.clone());
-        let (logical_res, _scale_factor) = (app_view.physical_resolution(), app_view.scale_factor);
+        let (physical_res, scale_factor) = (app_view.physical_resolution(), app_view.scale_factor);
 
-        // Update resolution of bevy window
-        // I think scale is already handled in index.js by devicePixelRatio
-        window.resolution.set_scale_factor(1.0);
+        // index.js 按 devicePixelRatio 设置了画布的像素尺寸，
+        // window 的逻辑尺寸即画布的 CSS 尺寸
+        window.resolution.set_scale_factor(scale_factor);
         window
             .resolution
-            .set(logical_res.0 as f32, logical_res.1 as f32);
+            .set_physical_resolution(physical_res.0, physical_res.1);
 
         let raw_window_wrapper = RawHandleWrapper::new(&app_view);
         commands.entity(entity).insert(raw_window_wrapper.unwrap());
         writer.write(WindowCreated { window: entity });
-        break;
     }
 }
 
@@ -108,6 +119,15 @@ pub fn despawn_window(
     }
 }
 
-pub fn changed_window(mut _changed_windows: Query<(Entity, &mut Window), Changed<Window>>) {
-    // TODO:
+/// 把 `Window` 的尺寸与缩放同步到画布，surface 在渲染提取到新尺寸后重新配置
+pub fn changed_window(
+    changed_windows: Query<&Window, Changed<Window>>,
+    mut canvas: ResMut<OffscreenCanvas>,
+) {
+    for window in &changed_windows {
+        canvas.set_physical_resolution(window.physical_width(), window.physical_height());
+        if canvas.scale_factor != window.scale_factor() {
+            canvas.scale_factor = window.scale_factor();
+        }
+    }
 }
@@ -0,0 +1,335 @@
+//! 从鼠标按键中识别单击、双击与长按
+//!
+//! 按下后移动超过 [`ClickSettings::move_tolerance`] 视为拖动，松开时不产生单击；
+//! 按住不动超过 [`ClickSettings::long_press_duration`] 产生 [`LongPress`]，同样不再产生单击。
+//! 命中的对象取按下时悬停的对象，因此须在射线拾取之后运行，见 [`ClickSystems`]。
+//!
+//! 一帧内可能先后收到按下、移动与松开，按 `WindowEvent` 的顺序处理，
+//! 每次按键取其之前最近一次 `CursorMoved` 的位置
+
+use crate::bevy_app::Hovered;
+use bevy::{platform::collections::HashMap, prelude::*, window::WindowEvent};
+use bevy_input::ButtonState;
+use std::time::Duration;
+
+pub struct ClickPlugin;
+
+impl Plugin for ClickPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClickSettings>()
+            .add_message::<Click>()
+            .add_message::<DoubleClick>()
+            .add_message::<LongPress>()
+            .add_systems(Update, recognize_clicks.in_set(ClickSystems));
+    }
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ClickSystems;
+
+#[derive(Resource, Debug, Clone)]
+pub struct ClickSettings {
+    /// 按下到松开允许的移动距离（逻辑像素），双击的两次单击之间同样适用
+    pub move_tolerance: f32,
+    /// 两次单击的间隔不超过此时长时算双击
+    pub double_click_interval: Duration,
+    /// 按住多久算长按
+    pub long_press_duration: Duration,
+}
+
+impl Default for ClickSettings {
+    fn default() -> Self {
+        Self {
+            move_tolerance: 5.,
+            double_click_interval: Duration::from_millis(500),
+            long_press_duration: Duration::from_millis(500),
+        }
+    }
+}
+
+/// 单击，在松开时产生
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct Click {
+    pub button: MouseButton,
+    /// 按下时的逻辑坐标
+    pub position: Vec2,
+    pub entity: Option<Entity>,
+    /// 连续单击的次数，与 DOM 的 `UIEvent.detail` 一致
+    pub count: u32,
+}
+
+/// 第二次单击时紧随 [`Click`] 产生
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct DoubleClick {
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub entity: Option<Entity>,
+}
+
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct LongPress {
+    pub button: MouseButton,
+    pub position: Vec2,
+    pub entity: Option<Entity>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    position: Vec2,
+    entity: Option<Entity>,
+    start: Duration,
+    /// 已移动超出容差或已触发长按，松开时不再是单击
+    cancelled: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LastClick {
+    button: MouseButton,
+    position: Vec2,
+    entity: Option<Entity>,
+    time: Duration,
+    count: u32,
+}
+
+#[derive(Default)]
+struct Recognizer {
+    cursor: Option<Vec2>,
+    presses: HashMap<MouseButton, Press>,
+    last_click: Option<LastClick>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn recognize_clicks(
+    mut window_events: MessageReader<WindowEvent>,
+    hovered: Query<Entity, With<Hovered>>,
+    time: Res<Time>,
+    settings: Res<ClickSettings>,
+    mut state: Local<Recognizer>,
+    mut clicks: MessageWriter<Click>,
+    mut double_clicks: MessageWriter<DoubleClick>,
+    mut long_presses: MessageWriter<LongPress>,
+) {
+    let now = time.elapsed();
+    let within = |a: Vec2, b: Vec2| a.distance(b) <= settings.move_tolerance;
+    let state = &mut *state;
+
+    for ev in window_events.read() {
+        let ev = match ev {
+            WindowEvent::CursorMoved(moved) => {
+                state.cursor = Some(moved.position);
+                for press in state.presses.values_mut() {
+                    if !within(moved.position, press.position) {
+                        press.cancelled = true;
+                    }
+                }
+                continue;
+            }
+            WindowEvent::CursorLeft(_) => {
+                state.cursor = None;
+                continue;
+            }
+            WindowEvent::MouseButtonInput(ev) => ev,
+            _ => continue,
+        };
+        match ev.state {
+            ButtonState::Pressed => {
+                let Some(position) = state.cursor else {
+                    continue;
+                };
+                state.presses.insert(
+                    ev.button,
+                    Press {
+                        position,
+                        entity: hovered.iter().next(),
+                        start: now,
+                        cancelled: false,
+                    },
+                );
+            }
+            ButtonState::Released => {
+                let Some(press) = state.presses.remove(&ev.button) else {
+                    continue;
+                };
+                if press.cancelled {
+                    continue;
+                }
+                let count = match state.last_click {
+                    Some(last)
+                        if last.button == ev.button
+                            && last.entity == press.entity
+                            && within(last.position, press.position)
+                            && now - last.time <= settings.double_click_interval =>
+                    {
+                        last.count + 1
+                    }
+                    _ => 1,
+                };
+                state.last_click = Some(LastClick {
+                    button: ev.button,
+                    position: press.position,
+                    entity: press.entity,
+                    time: now,
+                    count,
+                });
+                clicks.write(Click {
+                    button: ev.button,
+                    position: press.position,
+                    entity: press.entity,
+                    count,
+                });
+                if count == 2 {
+                    double_clicks.write(DoubleClick {
+                        button: ev.button,
+                        position: press.position,
+                        entity: press.entity,
+                    });
+                }
+            }
+        }
+    }
+
+    for (&button, press) in state.presses.iter_mut() {
+        if !press.cancelled && now - press.start >= settings.long_press_duration {
+            press.cancelled = true;
+            long_presses.write(LongPress {
+                button,
+                position: press.position,
+                entity: press.entity,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{HostToWorker, PointerEvent, PointerType},
+        test_harness::{Harness, pointer},
+    };
+    use bevy::time::TimeUpdateStrategy;
+
+    fn harness() -> Harness {
+        Harness::with_plugins(1.0, |app| {
+            app.add_plugins(ClickPlugin)
+                .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                    100,
+                )));
+        })
+    }
+
+    fn down(x: f32, y: f32) -> HostToWorker {
+        HostToWorker::PointerDown(PointerEvent {
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, x, y)
+        })
+    }
+
+    fn moved(x: f32, y: f32) -> HostToWorker {
+        HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, x, y)
+        })
+    }
+
+    fn up(x: f32, y: f32) -> HostToWorker {
+        HostToWorker::PointerUp(pointer(PointerType::Mouse, 1, x, y))
+    }
+
+    #[test]
+    fn click_and_double_click() {
+        let mut h = harness();
+        let target = h.world_mut().spawn(Hovered {}).id();
+
+        h.send(down(10., 10.));
+        h.send(moved(12., 11.));
+        assert!(h.read::<Click>().is_empty());
+        h.send(up(12., 11.));
+        assert_eq!(
+            h.read::<Click>(),
+            vec![Click {
+                button: MouseButton::Left,
+                position: Vec2::new(10., 10.),
+                entity: Some(target),
+                count: 1,
+            }]
+        );
+        assert!(h.read::<DoubleClick>().is_empty());
+
+        h.send(down(11., 10.));
+        h.send(up(11., 10.));
+        assert_eq!(h.read::<Click>()[0].count, 2);
+        assert_eq!(
+            h.read::<DoubleClick>(),
+            vec![DoubleClick {
+                button: MouseButton::Left,
+                position: Vec2::new(11., 10.),
+                entity: Some(target),
+            }]
+        );
+
+        // 间隔过长重新计数
+        for _ in 0..5 {
+            h.frame();
+        }
+        h.send(down(11., 10.));
+        h.send(up(11., 10.));
+        assert_eq!(h.read::<Click>()[0].count, 1);
+    }
+
+    #[test]
+    fn drag_is_not_a_click() {
+        let mut h = harness();
+
+        h.send(down(10., 10.));
+        h.send(moved(30., 10.));
+        h.send(moved(10., 10.));
+        h.send(up(10., 10.));
+        assert!(h.read::<Click>().is_empty());
+
+        // 按下与松开在同一帧
+        h.bridge.send(down(50., 50.));
+        h.send(up(50., 50.));
+        assert_eq!(h.read::<Click>().len(), 1);
+
+        // 按下、移动与松开在同一帧，移动同样取消单击
+        h.bridge.send(down(50., 50.));
+        h.bridge.send(moved(70., 50.));
+        h.send(up(50., 50.));
+        assert!(h.read::<Click>().is_empty());
+
+        // 位置取按下时而非帧末的光标
+        h.bridge.send(down(80., 80.));
+        h.bridge.send(moved(82., 80.));
+        h.send(up(83., 80.));
+        assert_eq!(h.read::<Click>()[0].position, Vec2::new(80., 80.));
+    }
+
+    #[test]
+    fn long_press() {
+        let mut h = harness();
+
+        h.send(down(10., 10.));
+        for _ in 0..4 {
+            h.send(moved(11., 10.));
+        }
+        assert!(h.read::<LongPress>().is_empty());
+
+        h.frame();
+        assert_eq!(
+            h.read::<LongPress>(),
+            vec![LongPress {
+                button: MouseButton::Left,
+                position: Vec2::new(10., 10.),
+                entity: None,
+            }]
+        );
+        for _ in 0..5 {
+            h.frame();
+        }
+        h.send(up(11., 10.));
+        assert!(h.read::<LongPress>().is_empty());
+        assert!(h.read::<Click>().is_empty());
+    }
+}
@@ -0,0 +1,225 @@
+//! 剪贴板
+//!
+//! worker 中没有 `navigator.clipboard`：system 通过 [`Clipboard`] 发起复制或粘贴，
+//! 请求在帧末以 [`WorkerToHost::ClipboardWrite`] / [`WorkerToHost::ClipboardRead`] 发给宿主，
+//! 宿主以 [`HostToWorker::Clipboard`](crate::protocol::HostToWorker::Clipboard) 回应，
+//! 按请求 id 配对后产生 [`ClipboardResponse`]
+
+use crate::{
+    WorkerApp,
+    bridge::Host,
+    protocol::{ClipboardResult, WorkerToHost},
+};
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::{Serialize, de::DeserializeOwned};
+
+pub struct ClipboardPlugin;
+
+impl Plugin for ClipboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Clipboard>()
+            .add_message::<ClipboardResponse>()
+            .add_systems(Last, post_clipboard_requests);
+    }
+}
+
+/// 一次剪贴板请求，与对应的 [`ClipboardResponse::request`] 相等
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ClipboardRequest(u32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestKind {
+    Copy,
+    Paste,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct Clipboard {
+    next_id: u32,
+    /// 尚未发给宿主的请求
+    queued: Vec<WorkerToHost>,
+    /// 已发出、等待宿主回应的请求
+    pending: HashMap<u32, RequestKind>,
+}
+
+impl Clipboard {
+    pub fn copy_text(&mut self, text: impl Into<String>) -> ClipboardRequest {
+        let id = self.request(RequestKind::Copy);
+        self.queued.push(WorkerToHost::ClipboardWrite {
+            id,
+            text: text.into(),
+        });
+        ClipboardRequest(id)
+    }
+
+    /// 序列化为 JSON 文本后复制
+    pub fn copy_json<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> serde_json::Result<ClipboardRequest> {
+        let text = serde_json::to_string(value)?;
+        Ok(self.copy_text(text))
+    }
+
+    /// 读取剪贴板中的文本
+    pub fn paste(&mut self) -> ClipboardRequest {
+        let id = self.request(RequestKind::Paste);
+        self.queued.push(WorkerToHost::ClipboardRead { id });
+        ClipboardRequest(id)
+    }
+
+    /// 请求尚未得到回应
+    pub fn is_pending(&self, request: ClipboardRequest) -> bool {
+        self.pending.contains_key(&request.0)
+    }
+
+    fn request(&mut self, kind: RequestKind) -> u32 {
+        let id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+        self.pending.insert(id, kind);
+        id
+    }
+}
+
+/// 宿主对剪贴板请求的回应
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct ClipboardResponse {
+    pub request: ClipboardRequest,
+    pub result: ClipboardOutcome,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClipboardOutcome {
+    Copied,
+    Pasted(String),
+    /// 没有权限、页面未获得焦点等，内容为宿主给出的原因
+    Failed(String),
+}
+
+impl ClipboardResponse {
+    /// 粘贴得到的文本
+    pub fn text(&self) -> Option<&str> {
+        match &self.result {
+            ClipboardOutcome::Pasted(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// 把粘贴得到的文本解析为 JSON，不是粘贴结果或解析失败时为 `None`
+    pub fn json<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_str(self.text()?).ok()
+    }
+}
+
+impl WorkerApp {
+    pub(crate) fn on_clipboard(&mut self, ev: ClipboardResult) {
+        let world = self.world_mut();
+        let Some(mut clipboard) = world.get_resource_mut::<Clipboard>() else {
+            return;
+        };
+        let Some(kind) = clipboard.pending.remove(&ev.id) else {
+            warn!("[worker] unknown clipboard request: {}", ev.id);
+            return;
+        };
+        let result = match (ev.error, kind) {
+            (Some(error), _) => ClipboardOutcome::Failed(error),
+            (None, RequestKind::Copy) => ClipboardOutcome::Copied,
+            (None, RequestKind::Paste) => ClipboardOutcome::Pasted(ev.text.unwrap_or_default()),
+        };
+        world.write_message(ClipboardResponse {
+            request: ClipboardRequest(ev.id),
+            result,
+        });
+    }
+}
+
+fn post_clipboard_requests(mut clipboard: ResMut<Clipboard>, host: Res<Host>) {
+    if clipboard.queued.is_empty() {
+        return;
+    }
+    for msg in clipboard.queued.drain(..) {
+        host.post(msg);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{protocol::HostToWorker, test_harness::Harness};
+
+    fn harness() -> Harness {
+        Harness::with_plugins(1.0, |app| {
+            app.add_plugins(ClipboardPlugin);
+        })
+    }
+
+    fn reply(id: u32, text: Option<&str>, error: Option<&str>) -> HostToWorker {
+        HostToWorker::Clipboard(ClipboardResult {
+            id,
+            text: text.map(Into::into),
+            error: error.map(Into::into),
+        })
+    }
+
+    #[test]
+    fn copy_and_paste_round_trip() {
+        let mut h = harness();
+
+        let mut clipboard = h.world_mut().resource_mut::<Clipboard>();
+        let copy = clipboard.copy_json(&[1, 2, 3]).unwrap();
+        let paste = clipboard.paste();
+        assert!(h.bridge.take_posted().is_empty());
+
+        h.frame();
+        assert_eq!(
+            h.bridge.take_posted(),
+            vec![
+                WorkerToHost::ClipboardWrite {
+                    id: 0,
+                    text: "[1,2,3]".into()
+                },
+                WorkerToHost::ClipboardRead { id: 1 },
+            ]
+        );
+
+        // 回应的顺序不必与请求一致
+        h.bridge.send(reply(1, Some("[4,5]"), None));
+        h.send(reply(0, None, None));
+        let responses = h.read::<ClipboardResponse>();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0].request, paste);
+        assert_eq!(responses[0].json::<Vec<i32>>(), Some(vec![4, 5]));
+        assert_eq!(
+            responses[1],
+            ClipboardResponse {
+                request: copy,
+                result: ClipboardOutcome::Copied
+            }
+        );
+        let clipboard = h.world().resource::<Clipboard>();
+        assert!(!clipboard.is_pending(copy) && !clipboard.is_pending(paste));
+    }
+
+    #[test]
+    fn failures_and_unknown_replies() {
+        let mut h = harness();
+
+        let paste = h.world_mut().resource_mut::<Clipboard>().paste();
+        h.frame();
+        h.bridge.take_posted();
+
+        h.bridge.send(reply(7, Some("stale"), None));
+        h.send(reply(0, None, Some("NotAllowedError")));
+        assert_eq!(
+            h.read::<ClipboardResponse>(),
+            vec![ClipboardResponse {
+                request: paste,
+                result: ClipboardOutcome::Failed("NotAllowedError".into())
+            }]
+        );
+
+        // 同一请求只回应一次
+        h.send(reply(0, Some("again"), None));
+        assert!(h.read::<ClipboardResponse>().is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+//! 合并同一帧内的指针移动
+//!
+//! 宿主的消息在每帧开始时一次取出。同一指针的连续 pointermove 只保留最终状态，
+//! 减少 `CursorMoved` 与随之而来的射线拾取；需要完整轨迹（如绘画）时可保留全部采样点。
+//! 旧版宿主的 mousemove 同样合并；它没有采样点，要求保留采样点时不合并
+
+use crate::{
+    WorkerApp,
+    protocol::{HostToWorker, MouseEvent, PointerEvent},
+};
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct InputCoalescing {
+    /// 合并同一指针连续的移动
+    pub enabled: bool,
+    /// 合并时保留全部采样点（含 getCoalescedEvents 的），只减少消息的处理次数
+    pub keep_samples: bool,
+}
+
+impl Default for InputCoalescing {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keep_samples: false,
+        }
+    }
+}
+
+/// 输入合并的统计
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct InputCoalescingStats {
+    /// 最近一帧收到的消息数
+    pub frame_received: usize,
+    /// 最近一帧被合并掉的消息数
+    pub frame_merged: usize,
+    /// 累计收到的消息数
+    pub received: u64,
+    /// 累计被合并掉的消息数
+    pub merged: u64,
+}
+
+impl WorkerApp {
+    /// 合并一帧内取出的宿主消息，并记录统计
+    pub(crate) fn coalesce(&mut self, batch: Vec<HostToWorker>) -> Vec<HostToWorker> {
+        let settings = self.world().resource::<InputCoalescing>().clone();
+        let received = batch.len();
+        let batch = if settings.enabled {
+            coalesce(batch, settings.keep_samples)
+        } else {
+            batch
+        };
+
+        let merged = received - batch.len();
+        let mut stats = self.world_mut().resource_mut::<InputCoalescingStats>();
+        stats.frame_received = received;
+        stats.frame_merged = merged;
+        stats.received += received as u64;
+        stats.merged += merged as u64;
+        batch
+    }
+}
+
+fn coalesce(batch: Vec<HostToWorker>, keep_samples: bool) -> Vec<HostToWorker> {
+    let mut out: Vec<HostToWorker> = Vec::with_capacity(batch.len());
+    // 当前这段连续移动的起点：其中不同指针的移动互不影响，顺序可以交换
+    let mut run_start = 0;
+    for msg in batch {
+        let mut ev = match msg {
+            HostToWorker::PointerMove(ev) => ev,
+            // 只与紧邻的上一个 mousemove 合并，不越过指针事件
+            HostToWorker::MouseMove(ev) => {
+                match out.last_mut() {
+                    Some(HostToWorker::MouseMove(prev))
+                        if !keep_samples && mouse_mergeable(prev, &ev) =>
+                    {
+                        merge_mouse(prev, ev)
+                    }
+                    _ => out.push(HostToWorker::MouseMove(ev)),
+                }
+                continue;
+            }
+            msg => {
+                out.push(msg);
+                run_start = out.len();
+                continue;
+            }
+        };
+        if !keep_samples {
+            collapse(&mut ev);
+        }
+        let prev = out[run_start..].iter_mut().rev().find_map(|msg| match msg {
+            HostToWorker::PointerMove(prev) if prev.pointer_id == ev.pointer_id => Some(prev),
+            _ => None,
+        });
+        match prev {
+            Some(prev) if mergeable(prev, &ev) => {
+                merge(prev, ev);
+                if !keep_samples {
+                    collapse(prev);
+                }
+            }
+            _ => out.push(HostToWorker::PointerMove(ev)),
+        }
+    }
+    out
+}
+
+/// 按钮与修饰键都不变时才能合并，否则会丢失移动过程中的按下/松开
+fn mergeable(prev: &PointerEvent, ev: &PointerEvent) -> bool {
+    prev.pointer_type == ev.pointer_type
+        && prev.button == -1
+        && ev.button == -1
+        && prev.buttons == ev.buttons
+        && (prev.alt_key, prev.ctrl_key, prev.meta_key, prev.shift_key)
+            == (ev.alt_key, ev.ctrl_key, ev.meta_key, ev.shift_key)
+}
+
+fn mouse_mergeable(prev: &MouseEvent, ev: &MouseEvent) -> bool {
+    prev.buttons == ev.buttons
+        && (prev.alt_key, prev.ctrl_key, prev.meta_key, prev.shift_key)
+            == (ev.alt_key, ev.ctrl_key, ev.meta_key, ev.shift_key)
+}
+
+fn merge_mouse(prev: &mut MouseEvent, mut ev: MouseEvent) {
+    ev.movement_x += prev.movement_x;
+    ev.movement_y += prev.movement_y;
+    *prev = ev;
+}
+
+/// 只保留最后一个采样点，位移为全部采样点之和
+fn collapse(ev: &mut PointerEvent) {
+    with_samples(ev);
+    let movement = movement(&ev.coalesced);
+    let mut last = ev.coalesced.pop().unwrap();
+    (last.movement_x, last.movement_y) = movement;
+    (ev.movement_x, ev.movement_y) = movement;
+    ev.coalesced = vec![last];
+}
+
+/// 以 `ev` 为准，位移累加，采样点按时间拼接
+fn merge(prev: &mut PointerEvent, mut ev: PointerEvent) {
+    with_samples(prev);
+    with_samples(&mut ev);
+    let mut coalesced = std::mem::take(&mut prev.coalesced);
+    coalesced.append(&mut ev.coalesced);
+    (ev.movement_x, ev.movement_y) = movement(&coalesced);
+    ev.coalesced = coalesced;
+    *prev = ev;
+}
+
+/// 没有合并事件时，事件自身就是唯一的采样点
+fn with_samples(ev: &mut PointerEvent) {
+    if ev.coalesced.is_empty() {
+        ev.coalesced.push(ev.clone());
+    }
+}
+
+fn movement(samples: &[PointerEvent]) -> (f32, f32) {
+    samples
+        .iter()
+        .fold((0., 0.), |(x, y), s| (x + s.movement_x, y + s.movement_y))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::PointerType,
+        test_harness::{Harness, mouse, pointer},
+    };
+    use bevy_input::{
+        mouse::{MouseButtonInput, MouseMotion},
+        touch::TouchInput,
+    };
+
+    fn mouse_move(x: f32, y: f32, movement: f32) -> HostToWorker {
+        HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            movement_x: movement,
+            coalesced: vec![
+                PointerEvent {
+                    movement_x: movement / 2.,
+                    ..pointer(PointerType::Mouse, 1, x - 1., y)
+                },
+                PointerEvent {
+                    movement_x: movement / 2.,
+                    ..pointer(PointerType::Mouse, 1, x, y)
+                },
+            ],
+            ..pointer(PointerType::Mouse, 1, x, y)
+        })
+    }
+
+    fn touch_move(id: i32, x: f32) -> HostToWorker {
+        HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            ..pointer(PointerType::Touch, id, x, 0.)
+        })
+    }
+
+    #[test]
+    fn merges_moves_within_a_frame() {
+        let mut h = Harness::new(1.0);
+
+        h.bridge.send(mouse_move(10., 0., 2.));
+        h.bridge.send(mouse_move(20., 0., 4.));
+        h.send(mouse_move(30., 0., 6.));
+        let moved: Vec<_> = h.read::<CursorMoved>().iter().map(|c| c.position).collect();
+        assert_eq!(moved, vec![Vec2::new(30., 0.)]);
+        // 位移不因合并而丢失
+        assert_eq!(
+            h.read::<MouseMotion>(),
+            vec![MouseMotion {
+                delta: Vec2::new(12., 0.)
+            }]
+        );
+
+        let stats = h.world().resource::<InputCoalescingStats>();
+        assert_eq!((stats.frame_received, stats.frame_merged), (3, 2));
+        assert_eq!((stats.received, stats.merged), (3, 2));
+
+        h.frame();
+        let stats = h.world().resource::<InputCoalescingStats>();
+        assert_eq!((stats.frame_received, stats.frame_merged), (0, 0));
+        assert_eq!((stats.received, stats.merged), (3, 2));
+    }
+
+    #[test]
+    fn merges_legacy_mouse_moves() {
+        let mut h = Harness::new(1.0);
+        let legacy = |x: f32, buttons: u16| {
+            HostToWorker::MouseMove(MouseEvent {
+                buttons,
+                movement_x: 2.,
+                ..mouse(0, x, 0.)
+            })
+        };
+
+        h.bridge.send(legacy(10., 0));
+        h.bridge.send(legacy(12., 0));
+        h.bridge.send(legacy(14., 0));
+        // 按钮改变时不合并
+        h.bridge.send(legacy(16., 1));
+        h.bridge.send(HostToWorker::MouseUp(mouse(0, 16., 0.)));
+        h.send(legacy(18., 0));
+        let moved: Vec<_> = h
+            .read::<CursorMoved>()
+            .iter()
+            .map(|c| c.position.x)
+            .collect();
+        assert_eq!(moved, vec![14., 16., 18.]);
+        let motion: Vec<_> = h.read::<MouseMotion>().iter().map(|m| m.delta.x).collect();
+        assert_eq!(motion, vec![6., 2., 2.]);
+        assert_eq!(h.world().resource::<InputCoalescingStats>().frame_merged, 2);
+    }
+
+    #[test]
+    fn keeps_samples_when_requested() {
+        let mut h = Harness::new(1.0);
+        h.world_mut().resource_mut::<InputCoalescing>().keep_samples = true;
+
+        h.bridge.send(mouse_move(10., 0., 2.));
+        h.send(mouse_move(20., 0., 4.));
+        let moved: Vec<_> = h
+            .read::<CursorMoved>()
+            .iter()
+            .map(|c| c.position.x)
+            .collect();
+        assert_eq!(moved, vec![9., 10., 19., 20.]);
+        let motion: f32 = h.read::<MouseMotion>().iter().map(|m| m.delta.x).sum();
+        assert_eq!(motion, 6.);
+        assert_eq!(h.world().resource::<InputCoalescingStats>().frame_merged, 1);
+    }
+
+    #[test]
+    fn button_changes_are_not_merged() {
+        let mut h = Harness::new(1.0);
+
+        h.bridge.send(mouse_move(10., 0., 0.));
+        h.bridge.send(HostToWorker::PointerDown(PointerEvent {
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, 10., 0.)
+        }));
+        h.bridge.send(HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, 11., 0.)
+        }));
+        // 移动中按下右键
+        h.bridge.send(HostToWorker::PointerMove(PointerEvent {
+            button: 2,
+            buttons: 3,
+            ..pointer(PointerType::Mouse, 1, 12., 0.)
+        }));
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            buttons: 3,
+            ..pointer(PointerType::Mouse, 1, 13., 0.)
+        }));
+        assert_eq!(h.read::<MouseButtonInput>().len(), 2);
+        assert_eq!(h.read::<CursorMoved>().len(), 5);
+        assert_eq!(h.world().resource::<InputCoalescingStats>().frame_merged, 0);
+    }
+
+    #[test]
+    fn interleaved_touches() {
+        let mut h = Harness::new(1.0);
+        h.bridge.send(HostToWorker::PointerDown(pointer(
+            PointerType::Touch,
+            1,
+            0.,
+            0.,
+        )));
+        h.send(HostToWorker::PointerDown(pointer(
+            PointerType::Touch,
+            2,
+            100.,
+            0.,
+        )));
+        h.read::<TouchInput>();
+
+        for x in 1..=3 {
+            h.bridge.send(touch_move(1, x as f32));
+            h.bridge.send(touch_move(2, 100. + x as f32));
+        }
+        h.bridge.send(HostToWorker::PointerUp(pointer(
+            PointerType::Touch,
+            2,
+            103.,
+            0.,
+        )));
+        h.send(touch_move(1, 4.));
+        let input: Vec<_> = h
+            .read::<TouchInput>()
+            .iter()
+            .map(|t| (t.id, t.position.x))
+            .collect();
+        // 抬起前后的移动分别合并
+        assert_eq!(input, vec![(1, 3.), (2, 103.), (2, 103.), (1, 4.)]);
+        assert_eq!(h.world().resource::<InputCoalescingStats>().frame_merged, 4);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut h = Harness::new(1.0);
+        h.world_mut().resource_mut::<InputCoalescing>().enabled = false;
+
+        h.bridge.send(touch_move(1, 1.));
+        h.send(touch_move(1, 2.));
+        assert_eq!(h.read::<TouchInput>().len(), 2);
+        assert_eq!(h.world().resource::<InputCoalescingStats>().frame_merged, 0);
+    }
+}
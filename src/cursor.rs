@@ -0,0 +1,257 @@
+//! 把主窗口的 `CursorOptions` 与 `CursorIcon` 同步给宿主
+//!
+//! worker 中无法直接锁定指针或修改 CSS cursor：改变时发送 [`WorkerToHost::Cursor`]，
+//! 宿主以 [`HostToWorker::PointerLock`](crate::protocol::HostToWorker::PointerLock)
+//! 回报实际的锁定状态，worker 据此更新 `CursorOptions` 并产生 [`PointerLockChanged`]。
+//!
+//! 只支持 `SystemCursorIcon`：`CursorIcon::Custom` 的图片无法交给宿主，按默认光标处理并警告一次
+
+use crate::{
+    WorkerApp,
+    bridge::Host,
+    protocol::{CursorGrab, WorkerToHost},
+};
+use bevy::{
+    prelude::*,
+    window::{CursorGrabMode, CursorIcon, CursorOptions, PrimaryWindow, SystemCursorIcon},
+};
+
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PostedCursor>()
+            .add_message::<PointerLockChanged>()
+            .add_systems(Last, sync_cursor);
+    }
+}
+
+/// 宿主确认的指针锁定状态改变
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct PointerLockChanged {
+    pub window: Entity,
+    pub locked: bool,
+}
+
+/// 最近一次发给宿主的光标设置，相同时不重复发送
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct PostedCursor(Option<CursorState>);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CursorState {
+    grab_mode: CursorGrab,
+    visible: bool,
+    icon: &'static str,
+}
+
+impl WorkerApp {
+    /// 锁定失败或被用户退出时，`grab_mode` 回到 `None`
+    pub(crate) fn on_pointer_lock(&mut self, locked: bool) {
+        let window = self.window;
+        let world = self.world_mut();
+        let Some(mut options) = world.get_mut::<CursorOptions>(window) else {
+            return;
+        };
+        let grab_mode = if locked {
+            CursorGrabMode::Locked
+        } else if options.grab_mode == CursorGrabMode::Locked {
+            CursorGrabMode::None
+        } else {
+            options.grab_mode
+        };
+        if options.grab_mode != grab_mode {
+            options.grab_mode = grab_mode;
+        }
+
+        // 宿主已处于该状态，无需再发送
+        if let Some(mut posted) = world.get_resource_mut::<PostedCursor>()
+            && let Some(state) = &mut posted.0
+        {
+            state.grab_mode = cursor_grab(grab_mode);
+        }
+        world.write_message(PointerLockChanged { window, locked });
+    }
+}
+
+fn sync_cursor(
+    window: Single<(&CursorOptions, Option<&CursorIcon>), With<PrimaryWindow>>,
+    mut posted: ResMut<PostedCursor>,
+    host: Res<Host>,
+) {
+    let (options, icon) = *window;
+    let icon = match icon.map(CursorIcon::as_system) {
+        None => SystemCursorIcon::default(),
+        Some(Some(icon)) => *icon,
+        Some(None) => {
+            warn_once!("[worker] custom cursor icons are not supported, using the default cursor");
+            SystemCursorIcon::default()
+        }
+    };
+    let state = CursorState {
+        grab_mode: cursor_grab(options.grab_mode),
+        visible: options.visible,
+        icon: css_cursor(icon),
+    };
+    if posted.0 == Some(state) {
+        return;
+    }
+    posted.0 = Some(state);
+    host.post(WorkerToHost::Cursor {
+        grab_mode: state.grab_mode,
+        visible: state.visible,
+        icon: state.icon.to_string(),
+    });
+}
+
+fn cursor_grab(mode: CursorGrabMode) -> CursorGrab {
+    match mode {
+        CursorGrabMode::None => CursorGrab::None,
+        CursorGrabMode::Confined => CursorGrab::Confined,
+        CursorGrabMode::Locked => CursorGrab::Locked,
+    }
+}
+
+/// https://www.w3.org/TR/css-ui-3/#cursor
+fn css_cursor(icon: SystemCursorIcon) -> &'static str {
+    match icon {
+        SystemCursorIcon::Default => "default",
+        SystemCursorIcon::ContextMenu => "context-menu",
+        SystemCursorIcon::Help => "help",
+        SystemCursorIcon::Pointer => "pointer",
+        SystemCursorIcon::Progress => "progress",
+        SystemCursorIcon::Wait => "wait",
+        SystemCursorIcon::Cell => "cell",
+        SystemCursorIcon::Crosshair => "crosshair",
+        SystemCursorIcon::Text => "text",
+        SystemCursorIcon::VerticalText => "vertical-text",
+        SystemCursorIcon::Alias => "alias",
+        SystemCursorIcon::Copy => "copy",
+        SystemCursorIcon::Move => "move",
+        SystemCursorIcon::NoDrop => "no-drop",
+        SystemCursorIcon::NotAllowed => "not-allowed",
+        SystemCursorIcon::Grab => "grab",
+        SystemCursorIcon::Grabbing => "grabbing",
+        SystemCursorIcon::EResize => "e-resize",
+        SystemCursorIcon::NResize => "n-resize",
+        SystemCursorIcon::NeResize => "ne-resize",
+        SystemCursorIcon::NwResize => "nw-resize",
+        SystemCursorIcon::SResize => "s-resize",
+        SystemCursorIcon::SeResize => "se-resize",
+        SystemCursorIcon::SwResize => "sw-resize",
+        SystemCursorIcon::WResize => "w-resize",
+        SystemCursorIcon::EwResize => "ew-resize",
+        SystemCursorIcon::NsResize => "ns-resize",
+        SystemCursorIcon::NeswResize => "nesw-resize",
+        SystemCursorIcon::NwseResize => "nwse-resize",
+        SystemCursorIcon::ColResize => "col-resize",
+        SystemCursorIcon::RowResize => "row-resize",
+        SystemCursorIcon::AllScroll => "all-scroll",
+        SystemCursorIcon::ZoomIn => "zoom-in",
+        SystemCursorIcon::ZoomOut => "zoom-out",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{protocol::HostToWorker, test_harness::Harness};
+
+    fn posted_cursors(h: &Harness) -> Vec<WorkerToHost> {
+        h.bridge
+            .take_posted()
+            .into_iter()
+            .filter(|msg| matches!(msg, WorkerToHost::Cursor { .. }))
+            .collect()
+    }
+
+    fn cursor(grab_mode: CursorGrab, visible: bool, icon: &str) -> WorkerToHost {
+        WorkerToHost::Cursor {
+            grab_mode,
+            visible,
+            icon: icon.to_string(),
+        }
+    }
+
+    #[test]
+    fn posts_cursor_changes() {
+        let mut h = Harness::with_plugins(1.0, |app| {
+            app.add_plugins(CursorPlugin);
+        });
+        let window = h.window;
+
+        h.frame();
+        assert_eq!(
+            posted_cursors(&h),
+            vec![cursor(CursorGrab::None, true, "default")]
+        );
+        h.frame();
+        assert!(posted_cursors(&h).is_empty());
+
+        h.world_mut()
+            .entity_mut(window)
+            .insert(CursorIcon::from(SystemCursorIcon::Grabbing));
+        h.frame();
+        assert_eq!(
+            posted_cursors(&h),
+            vec![cursor(CursorGrab::None, true, "grabbing")]
+        );
+
+        let mut options = h.world_mut().get_mut::<CursorOptions>(window).unwrap();
+        options.grab_mode = CursorGrabMode::Locked;
+        options.visible = false;
+        h.frame();
+        assert_eq!(
+            posted_cursors(&h),
+            vec![cursor(CursorGrab::Locked, false, "grabbing")]
+        );
+    }
+
+    #[test]
+    fn pointer_lock_confirmation() {
+        let mut h = Harness::with_plugins(1.0, |app| {
+            app.add_plugins(CursorPlugin);
+        });
+        let window = h.window;
+
+        h.world_mut()
+            .get_mut::<CursorOptions>(window)
+            .unwrap()
+            .grab_mode = CursorGrabMode::Locked;
+        h.frame();
+        h.bridge.take_posted();
+
+        h.send(HostToWorker::PointerLock(true));
+        assert_eq!(
+            h.read::<PointerLockChanged>(),
+            vec![PointerLockChanged {
+                window,
+                locked: true
+            }]
+        );
+        assert!(posted_cursors(&h).is_empty());
+
+        // 用户按 Esc 退出锁定，不再回发
+        h.send(HostToWorker::PointerLock(false));
+        let options = h.world().get::<CursorOptions>(window).unwrap();
+        assert_eq!(options.grab_mode, CursorGrabMode::None);
+        assert_eq!(
+            h.read::<PointerLockChanged>(),
+            vec![PointerLockChanged {
+                window,
+                locked: false
+            }]
+        );
+        assert!(posted_cursors(&h).is_empty());
+
+        // 应用再次请求锁定
+        h.world_mut()
+            .get_mut::<CursorOptions>(window)
+            .unwrap()
+            .grab_mode = CursorGrabMode::Locked;
+        h.frame();
+        assert_eq!(
+            posted_cursors(&h),
+            vec![cursor(CursorGrab::Locked, true, "default")]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! 手柄：worker 中没有 `navigator.getGamepads()`，由宿主轮询后发来快照，
+//! 与上一次的快照比较后产生 bevy_input 的原始手柄消息，实体的管理方式与 bevy_gilrs 一致
+
+use crate::{WorkerApp, protocol::GamepadSnapshot};
+use bevy::{platform::collections::HashMap, prelude::*};
+use bevy_input::gamepad::{
+    GamepadAxis, GamepadButton, GamepadConnection, GamepadConnectionEvent,
+    RawGamepadAxisChangedEvent, RawGamepadButtonChangedEvent, RawGamepadEvent,
+};
+
+/// 宿主手柄与实体的对应关系
+#[derive(Resource, Debug, Default)]
+pub(crate) struct WebGamepads {
+    /// 断开后保留，同一位置重新连接时复用实体（保留其 `GamepadSettings`）
+    entities: HashMap<u32, Entity>,
+    /// 已连接手柄的上一次快照
+    connected: HashMap<u32, GamepadSnapshot>,
+}
+
+/// https://w3c.github.io/gamepad/#remapping
+const STANDARD_BUTTONS: [GamepadButton; 17] = [
+    GamepadButton::South,
+    GamepadButton::East,
+    GamepadButton::West,
+    GamepadButton::North,
+    GamepadButton::LeftTrigger,
+    GamepadButton::RightTrigger,
+    GamepadButton::LeftTrigger2,
+    GamepadButton::RightTrigger2,
+    GamepadButton::Select,
+    GamepadButton::Start,
+    GamepadButton::LeftThumb,
+    GamepadButton::RightThumb,
+    GamepadButton::DPadUp,
+    GamepadButton::DPadDown,
+    GamepadButton::DPadLeft,
+    GamepadButton::DPadRight,
+    GamepadButton::Mode,
+];
+
+const STANDARD_AXES: [GamepadAxis; 4] = [
+    GamepadAxis::LeftStickX,
+    GamepadAxis::LeftStickY,
+    GamepadAxis::RightStickX,
+    GamepadAxis::RightStickY,
+];
+
+impl WorkerApp {
+    pub(crate) fn on_gamepads(&mut self, snapshots: Vec<GamepadSnapshot>) {
+        let world = self.world_mut();
+        let mut pads = world.remove_resource::<WebGamepads>().unwrap_or_default();
+
+        // 消失或换成了另一个设备的位置视为断开
+        let gone: Vec<u32> = pads
+            .connected
+            .iter()
+            .filter(|(index, prev)| {
+                !snapshots
+                    .iter()
+                    .any(|s| s.index == **index && s.id == prev.id)
+            })
+            .map(|(index, _)| *index)
+            .collect();
+        for index in gone {
+            pads.connected.remove(&index);
+            let gamepad = pads.entities[&index];
+            write_raw(
+                world,
+                GamepadConnectionEvent::new(gamepad, GamepadConnection::Disconnected),
+            );
+        }
+
+        for snapshot in snapshots {
+            let gamepad = *pads
+                .entities
+                .entry(snapshot.index)
+                .or_insert_with(|| world.spawn_empty().id());
+            let prev = pads.connected.remove(&snapshot.index).unwrap_or_else(|| {
+                let (vendor_id, product_id) = vendor_product(&snapshot.id);
+                let connection = GamepadConnection::Connected {
+                    name: snapshot.id.clone(),
+                    vendor_id,
+                    product_id,
+                };
+                write_raw(world, GamepadConnectionEvent::new(gamepad, connection));
+                GamepadSnapshot::default()
+            });
+
+            let standard = snapshot.mapping == "standard";
+            for (i, &value) in snapshot.buttons.iter().enumerate() {
+                if prev.buttons.get(i).copied().unwrap_or(0.) == value {
+                    continue;
+                }
+                if let Some(button) = button(standard, i) {
+                    write_raw(
+                        world,
+                        RawGamepadButtonChangedEvent::new(gamepad, button, value),
+                    );
+                }
+            }
+            for (i, &value) in snapshot.axes.iter().enumerate() {
+                if prev.axes.get(i).copied().unwrap_or(0.) == value {
+                    continue;
+                }
+                if let Some((axis, value)) = axis(standard, i, value) {
+                    write_raw(world, RawGamepadAxisChangedEvent::new(gamepad, axis, value));
+                }
+            }
+            pads.connected.insert(snapshot.index, snapshot);
+        }
+
+        world.insert_resource(pads);
+    }
+}
+
+/// 与 bevy_gilrs 一样，同时写入 `RawGamepadEvent` 与具体的消息
+fn write_raw<M: Message + Clone + Into<RawGamepadEvent>>(world: &mut World, msg: M) {
+    world.write_message::<RawGamepadEvent>(msg.clone().into());
+    world.write_message(msg);
+}
+
+fn button(standard: bool, index: usize) -> Option<GamepadButton> {
+    match STANDARD_BUTTONS.get(index) {
+        Some(button) if standard => Some(*button),
+        _ => u8::try_from(index).ok().map(GamepadButton::Other),
+    }
+}
+
+/// 标准布局的 y 轴向下为正，bevy 向上为正
+fn axis(standard: bool, index: usize, value: f32) -> Option<(GamepadAxis, f32)> {
+    match STANDARD_AXES.get(index) {
+        Some(axis) if standard => Some((*axis, if index % 2 == 1 { -value } else { value })),
+        _ => u8::try_from(index)
+            .ok()
+            .map(|i| (GamepadAxis::Other(i), value)),
+    }
+}
+
+/// 从 `Gamepad.id` 中解析 USB vendor / product id：
+/// Chrome 为 `"名称 (... Vendor: 054c Product: 09cc)"`，Firefox 为 `"054c-09cc-名称"`
+fn vendor_product(id: &str) -> (Option<u16>, Option<u16>) {
+    let hex = |s: &str| u16::from_str_radix(s, 16).ok();
+    let after = |key: &str| {
+        id.find(key)
+            .and_then(|i| id[i + key.len()..].get(..4))
+            .and_then(hex)
+    };
+    if id.contains("Vendor: ") {
+        return (after("Vendor: "), after("Product: "));
+    }
+    match id.splitn(3, '-').collect::<Vec<_>>()[..] {
+        [vendor, product, _] if vendor.len() <= 4 && product.len() <= 4 => {
+            (hex(vendor), hex(product))
+        }
+        _ => (None, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{protocol::HostToWorker, test_harness::Harness};
+    use bevy_input::gamepad::Gamepad;
+
+    fn pad(index: u32, buttons: Vec<f32>, axes: Vec<f32>) -> GamepadSnapshot {
+        GamepadSnapshot {
+            index,
+            id: "Wireless Controller (STANDARD GAMEPAD Vendor: 054c Product: 09cc)".into(),
+            mapping: "standard".into(),
+            buttons,
+            axes,
+        }
+    }
+
+    #[test]
+    fn connect_press_and_disconnect() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::Gamepads(vec![pad(
+            0,
+            vec![0.; 17],
+            vec![0.; 4],
+        )]));
+        let connections = h.read::<GamepadConnectionEvent>();
+        assert_eq!(connections.len(), 1);
+        let gamepad = connections[0].gamepad;
+        assert_eq!(
+            connections[0].connection,
+            GamepadConnection::Connected {
+                name: "Wireless Controller (STANDARD GAMEPAD Vendor: 054c Product: 09cc)".into(),
+                vendor_id: Some(0x054c),
+                product_id: Some(0x09cc),
+            }
+        );
+        assert_eq!(h.read::<RawGamepadEvent>().len(), 1);
+        assert!(h.read::<RawGamepadButtonChangedEvent>().is_empty());
+        assert!(h.world().get::<Gamepad>(gamepad).is_some());
+
+        let mut buttons = vec![0.; 17];
+        buttons[0] = 1.;
+        buttons[7] = 0.5;
+        h.send(HostToWorker::Gamepads(vec![pad(
+            0,
+            buttons,
+            vec![0., 0.25, 0., 0.],
+        )]));
+        assert_eq!(
+            h.read::<RawGamepadButtonChangedEvent>(),
+            vec![
+                RawGamepadButtonChangedEvent::new(gamepad, GamepadButton::South, 1.),
+                RawGamepadButtonChangedEvent::new(gamepad, GamepadButton::RightTrigger2, 0.5),
+            ]
+        );
+        assert_eq!(
+            h.read::<RawGamepadAxisChangedEvent>(),
+            vec![RawGamepadAxisChangedEvent::new(
+                gamepad,
+                GamepadAxis::LeftStickY,
+                -0.25
+            )]
+        );
+        let state = h.world().get::<Gamepad>(gamepad).unwrap();
+        assert!(state.just_pressed(GamepadButton::South));
+        assert_eq!(state.left_stick(), Vec2::new(0., -0.25));
+
+        // 状态不变时不产生消息
+        h.read::<RawGamepadEvent>();
+        h.send(HostToWorker::Gamepads(vec![pad(
+            0,
+            vec![1., 0., 0., 0., 0., 0., 0., 0.5],
+            vec![0., 0.25, 0., 0.],
+        )]));
+        assert!(h.read::<RawGamepadEvent>().is_empty());
+
+        h.send(HostToWorker::Gamepads(vec![]));
+        assert_eq!(
+            h.read::<GamepadConnectionEvent>(),
+            vec![GamepadConnectionEvent::new(
+                gamepad,
+                GamepadConnection::Disconnected
+            )]
+        );
+        // 实体保留，以便重新连接时复用
+        assert!(h.world().get::<Gamepad>(gamepad).is_none());
+        assert!(h.world().get_entity(gamepad).is_ok());
+
+        h.send(HostToWorker::Gamepads(vec![pad(0, vec![], vec![])]));
+        assert_eq!(h.read::<GamepadConnectionEvent>()[0].gamepad, gamepad);
+    }
+
+    #[test]
+    fn device_replaced_at_same_index() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::Gamepads(vec![pad(1, vec![], vec![])]));
+        h.send(HostToWorker::Gamepads(vec![GamepadSnapshot {
+            id: "045e-028e-Microsoft X-Box 360 pad".into(),
+            ..pad(1, vec![], vec![])
+        }]));
+        let connections: Vec<_> = h
+            .read::<GamepadConnectionEvent>()
+            .into_iter()
+            .map(|c| c.connection)
+            .collect();
+        assert_eq!(
+            connections[1..],
+            [
+                GamepadConnection::Disconnected,
+                GamepadConnection::Connected {
+                    name: "045e-028e-Microsoft X-Box 360 pad".into(),
+                    vendor_id: Some(0x045e),
+                    product_id: Some(0x028e),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn non_standard_mapping() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::Gamepads(vec![GamepadSnapshot {
+            id: "Joystick".into(),
+            mapping: String::new(),
+            ..pad(0, vec![0., 1.], vec![0., 0.5])
+        }]));
+        let gamepad = h.read::<GamepadConnectionEvent>()[0].gamepad;
+        assert_eq!(
+            h.read::<RawGamepadButtonChangedEvent>(),
+            vec![RawGamepadButtonChangedEvent::new(
+                gamepad,
+                GamepadButton::Other(1),
+                1.
+            )]
+        );
+        assert_eq!(
+            h.read::<RawGamepadAxisChangedEvent>(),
+            vec![RawGamepadAxisChangedEvent::new(
+                gamepad,
+                GamepadAxis::Other(1),
+                0.5
+            )]
+        );
+    }
+
+    #[test]
+    fn parses_vendor_and_product() {
+        assert_eq!(
+            vendor_product("USB Gamepad (Vendor: 0079 Product: 0006)"),
+            (Some(0x0079), Some(0x0006))
+        );
+        assert_eq!(
+            vendor_product("54c-9cc-Wireless Controller"),
+            (Some(0x054c), Some(0x09cc))
+        );
+        assert_eq!(
+            vendor_product("Xbox 360 Controller (XInput STANDARD GAMEPAD)"),
+            (None, None)
+        );
+    }
+}
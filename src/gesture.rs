@@ -0,0 +1,274 @@
+//! 从多点触摸中识别手势
+//!
+//! 双指的缩放、旋转、平移复用 bevy 的 [`PinchGesture`]、[`RotationGesture`]、[`PanGesture`]，
+//! 与触控板产生的手势一致；单指长按产生 [`LongPressGesture`]，长按后继续移动同一根手指也作为平移。
+//! 单位与 bevy_winit 相同：旋转为角度，平移为物理像素
+
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_input::{
+    InputSystems,
+    gestures::{PanGesture, PinchGesture, RotationGesture},
+    touch::Touches,
+};
+use std::time::Duration;
+
+pub struct GesturePlugin;
+
+impl Plugin for GesturePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GestureSettings>()
+            .add_message::<LongPressGesture>()
+            .add_systems(PreUpdate, recognize_gestures.after(InputSystems));
+    }
+}
+
+#[derive(Resource, Debug, Clone)]
+pub struct GestureSettings {
+    /// 按住多久算长按
+    pub long_press_duration: Duration,
+    /// 长按期间允许的移动距离（逻辑像素）
+    pub long_press_tolerance: f32,
+}
+
+impl Default for GestureSettings {
+    fn default() -> Self {
+        Self {
+            long_press_duration: Duration::from_millis(500),
+            long_press_tolerance: 10.,
+        }
+    }
+}
+
+/// 单指长按
+#[derive(Message, Debug, Clone, PartialEq)]
+pub struct LongPressGesture {
+    /// 手指的 touch id
+    pub id: u64,
+    /// 逻辑坐标
+    pub position: Vec2,
+}
+
+#[derive(Default)]
+struct Recognizer {
+    /// 上一帧两根手指的 (id, 位置)
+    pair: Option<[(u64, Vec2); 2]>,
+    /// 长按候选：(id, 按下时刻)
+    press: Option<(u64, Duration)>,
+    /// 已触发长按、仍按住的手指：(id, 上一帧位置)
+    held: Option<(u64, Vec2)>,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn recognize_gestures(
+    touches: Res<Touches>,
+    time: Res<Time>,
+    settings: Res<GestureSettings>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    mut state: Local<Recognizer>,
+    mut pinch: MessageWriter<PinchGesture>,
+    mut rotation: MessageWriter<RotationGesture>,
+    mut pan: MessageWriter<PanGesture>,
+    mut long_press: MessageWriter<LongPressGesture>,
+) {
+    // touch 的位置为逻辑坐标，平移量换算为物理像素
+    let scale_factor = window.single().map_or(1., Window::scale_factor);
+    let mut pressed: Vec<_> = touches.iter().map(|t| (t.id(), t.position())).collect();
+    pressed.sort_by_key(|(id, _)| *id);
+
+    match pressed[..] {
+        [a, b] => {
+            state.press = None;
+            state.held = None;
+            if let Some([pa, pb]) = state.pair
+                && (pa.0, pb.0) == (a.0, b.0)
+            {
+                let (prev, cur) = (pb.1 - pa.1, b.1 - a.1);
+                if cur != prev && prev != Vec2::ZERO && cur != Vec2::ZERO {
+                    pinch.write(PinchGesture(cur.length() / prev.length() - 1.));
+                    // 屏幕坐标的 y 轴向下，取反后逆时针为正
+                    let angle = -prev.angle_to(cur).to_degrees();
+                    if angle != 0. {
+                        rotation.write(RotationGesture(angle));
+                    }
+                }
+                let delta = (a.1 + b.1 - pa.1 - pb.1) / 2.;
+                if delta != Vec2::ZERO {
+                    pan.write(PanGesture(delta * scale_factor));
+                }
+            }
+            state.pair = Some([a, b]);
+        }
+        [(id, position)] => {
+            state.pair = None;
+            if let Some((held, last)) = state.held {
+                if held == id {
+                    if position != last {
+                        pan.write(PanGesture((position - last) * scale_factor));
+                    }
+                    state.held = Some((id, position));
+                    return;
+                }
+                state.held = None;
+            }
+
+            if touches.just_pressed(id) {
+                state.press = Some((id, time.elapsed()));
+            }
+            let Some((candidate, start)) = state.press else {
+                return;
+            };
+            let moved = touches
+                .get_pressed(id)
+                .map_or(0., |touch| touch.distance().length());
+            if candidate != id || moved > settings.long_press_tolerance {
+                state.press = None;
+            } else if time.elapsed() - start >= settings.long_press_duration {
+                long_press.write(LongPressGesture { id, position });
+                state.press = None;
+                state.held = Some((id, position));
+            }
+        }
+        _ => *state = Recognizer::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{HostToWorker, PointerType},
+        test_harness::{Harness, pointer},
+    };
+    use bevy::time::TimeUpdateStrategy;
+
+    fn harness() -> Harness {
+        harness_with_scale(1.0)
+    }
+
+    fn harness_with_scale(scale_factor: f32) -> Harness {
+        Harness::with_plugins(scale_factor, |app| {
+            app.add_plugins(GesturePlugin)
+                .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                    100,
+                )));
+        })
+    }
+
+    fn touch(id: i32, x: f32, y: f32) -> crate::protocol::PointerEvent {
+        pointer(PointerType::Touch, id, x, y)
+    }
+
+    #[test]
+    fn two_finger_gestures() {
+        let mut h = harness();
+
+        h.send(HostToWorker::PointerDown(touch(1, 100., 100.)));
+        h.send(HostToWorker::PointerDown(touch(2, 200., 100.)));
+        assert!(h.read::<PinchGesture>().is_empty());
+
+        // 张开到两倍距离
+        h.send(HostToWorker::PointerMove(touch(2, 300., 100.)));
+        assert_eq!(h.read::<PinchGesture>(), vec![PinchGesture(1.)]);
+        assert!(h.read::<RotationGesture>().is_empty());
+        assert_eq!(h.read::<PanGesture>(), vec![PanGesture(Vec2::new(50., 0.))]);
+
+        // 屏幕上顺时针转 90 度
+        h.send(HostToWorker::PointerMove(touch(2, 100., 300.)));
+        let rotation = h.read::<RotationGesture>();
+        assert_eq!(rotation.len(), 1);
+        assert!((rotation[0].0 + 90.).abs() < 1e-3);
+        assert!(h.read::<PinchGesture>()[0].0.abs() < 1e-5);
+        assert_eq!(
+            h.read::<PanGesture>(),
+            vec![PanGesture(Vec2::new(-100., 100.))]
+        );
+
+        // 两指同向移动只平移
+        h.bridge
+            .send(HostToWorker::PointerMove(touch(1, 110., 110.)));
+        h.send(HostToWorker::PointerMove(touch(2, 110., 310.)));
+        assert!(h.read::<PinchGesture>().is_empty());
+        assert!(h.read::<RotationGesture>().is_empty());
+        assert_eq!(
+            h.read::<PanGesture>(),
+            vec![PanGesture(Vec2::new(10., 10.))]
+        );
+
+        // 抬起一指后不再产生双指手势
+        h.send(HostToWorker::PointerUp(touch(2, 110., 310.)));
+        h.send(HostToWorker::PointerMove(touch(1, 150., 150.)));
+        assert!(h.read::<PanGesture>().is_empty());
+        assert!(h.read::<LongPressGesture>().is_empty());
+    }
+
+    #[test]
+    fn pan_in_physical_pixels() {
+        let mut h = harness_with_scale(2.0);
+
+        h.send(HostToWorker::PointerDown(touch(1, 10., 10.)));
+        h.send(HostToWorker::PointerDown(touch(2, 20., 10.)));
+        h.bridge.send(HostToWorker::PointerMove(touch(1, 15., 10.)));
+        h.send(HostToWorker::PointerMove(touch(2, 25., 10.)));
+        assert_eq!(h.read::<PanGesture>(), vec![PanGesture(Vec2::new(10., 0.))]);
+    }
+
+    #[test]
+    fn long_press_then_drag() {
+        let mut h = harness();
+
+        h.send(HostToWorker::PointerDown(touch(3, 50., 60.)));
+        for _ in 0..4 {
+            h.send(HostToWorker::PointerMove(touch(3, 52., 61.)));
+        }
+        assert!(h.read::<LongPressGesture>().is_empty());
+
+        h.send(HostToWorker::PointerMove(touch(3, 52., 61.)));
+        assert_eq!(
+            h.read::<LongPressGesture>(),
+            vec![LongPressGesture {
+                id: 3,
+                position: Vec2::new(52., 61.)
+            }]
+        );
+        assert!(h.read::<PanGesture>().is_empty());
+
+        h.send(HostToWorker::PointerMove(touch(3, 80., 61.)));
+        assert_eq!(h.read::<PanGesture>(), vec![PanGesture(Vec2::new(28., 0.))]);
+
+        // 只触发一次
+        for _ in 0..10 {
+            h.send(HostToWorker::PointerMove(touch(3, 80., 61.)));
+        }
+        assert!(h.read::<LongPressGesture>().is_empty());
+    }
+
+    #[test]
+    fn long_press_cancelled() {
+        let mut h = harness();
+
+        // 移动超出容差
+        h.send(HostToWorker::PointerDown(touch(1, 0., 0.)));
+        h.send(HostToWorker::PointerMove(touch(1, 20., 0.)));
+        for _ in 0..10 {
+            h.send(HostToWorker::PointerMove(touch(1, 20., 0.)));
+        }
+        h.send(HostToWorker::PointerUp(touch(1, 20., 0.)));
+
+        // 第二根手指落下
+        h.send(HostToWorker::PointerDown(touch(2, 0., 0.)));
+        h.send(HostToWorker::PointerDown(touch(3, 50., 0.)));
+        h.send(HostToWorker::PointerUp(touch(3, 50., 0.)));
+        for _ in 0..10 {
+            h.send(HostToWorker::PointerMove(touch(2, 0., 0.)));
+        }
+
+        // 过早抬起
+        h.send(HostToWorker::PointerUp(touch(2, 0., 0.)));
+        h.send(HostToWorker::PointerDown(touch(4, 0., 0.)));
+        h.send(HostToWorker::PointerUp(touch(4, 0., 0.)));
+        for _ in 0..10 {
+            h.frame();
+        }
+        assert!(h.read::<LongPressGesture>().is_empty());
+    }
+}
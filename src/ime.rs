@@ -0,0 +1,330 @@
+//! 输入法
+//!
+//! worker 无法接收文本输入：`Window.ime_enabled` 为 true 时通知宿主，
+//! 宿主把一个隐藏的 `<input>` 放到 `ime_position` 处并聚焦，
+//! 其 composition 事件转为 `Ime::Preedit` / `Ime::Commit`。组字期间的按键不产生 `KeyboardInput`
+
+use crate::{
+    WorkerApp,
+    bridge::Host,
+    protocol::{CompositionEvent, WorkerToHost},
+    write_window_event,
+};
+use bevy::{
+    platform::collections::HashSet,
+    prelude::*,
+    window::{Ime, PrimaryWindow, WindowEvent},
+};
+
+pub struct ImePlugin;
+
+impl Plugin for ImePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PostedIme>().add_systems(Last, sync_ime);
+    }
+}
+
+/// 最近一次发给宿主的 (ime_enabled, ime_position)
+#[derive(Resource, Debug, Default)]
+struct PostedIme(Option<(bool, Vec2)>);
+
+#[derive(Debug, Default)]
+pub(crate) struct ImeState {
+    composing: bool,
+    /// 组字期间被吞掉按下的键，其松开同样不发出
+    swallowed: HashSet<KeyCode>,
+}
+
+impl ImeState {
+    /// 组字中的按下不作为按键，其松开同样不发出
+    pub(crate) fn swallow_press(&mut self, key_code: KeyCode, composing: bool) -> bool {
+        if self.composing || composing {
+            self.swallowed.insert(key_code);
+            true
+        } else {
+            // 松开未送达时，不能吞掉这次按下对应的松开
+            self.swallowed.remove(&key_code);
+            false
+        }
+    }
+
+    pub(crate) fn swallow_release(&mut self, key_code: KeyCode) -> bool {
+        self.swallowed.remove(&key_code)
+    }
+
+    /// 返回是否正在组字
+    fn reset(&mut self) -> bool {
+        self.swallowed.clear();
+        std::mem::take(&mut self.composing)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CompositionPhase {
+    Start,
+    Update,
+    End,
+}
+
+impl WorkerApp {
+    pub(crate) fn on_composition(&mut self, phase: CompositionPhase, ev: CompositionEvent) {
+        let window = self.window;
+        let preedit = |value: String| Ime::Preedit {
+            window,
+            cursor: (!value.is_empty()).then_some((value.len(), value.len())),
+            value,
+        };
+        let messages = match phase {
+            CompositionPhase::Start => {
+                self.ime.composing = true;
+                vec![preedit(String::new())]
+            }
+            CompositionPhase::Update => vec![preedit(ev.data)],
+            // 与 winit 一致，提交前先清空预编辑文本
+            CompositionPhase::End => {
+                self.ime.composing = false;
+                let mut messages = vec![preedit(String::new())];
+                if !ev.data.is_empty() {
+                    messages.push(Ime::Commit {
+                        window,
+                        value: ev.data,
+                    });
+                }
+                messages
+            }
+        };
+        let world = self.world_mut();
+        for msg in messages {
+            write_window_event(world, msg);
+        }
+    }
+
+    /// 失去焦点时宿主不会再发来 compositionend，须自行结束组字并清空预编辑文本
+    pub(crate) fn cancel_composition(&mut self) {
+        if self.ime.reset() {
+            let window = self.window;
+            write_window_event(
+                self.world_mut(),
+                Ime::Preedit {
+                    window,
+                    value: String::new(),
+                    cursor: None,
+                },
+            );
+        }
+    }
+}
+
+fn sync_ime(
+    window: Single<(Entity, &Window), With<PrimaryWindow>>,
+    mut posted: ResMut<PostedIme>,
+    mut ime: MessageWriter<Ime>,
+    mut window_events: MessageWriter<WindowEvent>,
+    host: Res<Host>,
+) {
+    let (entity, window) = *window;
+    let state = (window.ime_enabled, window.ime_position);
+    if posted.0 == Some(state) {
+        return;
+    }
+    let was_enabled = posted.0.is_some_and(|(enabled, _)| enabled);
+    posted.0 = Some(state);
+    host.post(WorkerToHost::Ime {
+        enabled: state.0,
+        x: state.1.x,
+        y: state.1.y,
+    });
+    let msg = match (was_enabled, state.0) {
+        (false, true) => Ime::Enabled { window: entity },
+        (true, false) => Ime::Disabled { window: entity },
+        _ => return,
+    };
+    window_events.write(msg.clone().into());
+    ime.write(msg);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{HostToWorker, KeyboardEvent},
+        test_harness::{Harness, key},
+    };
+    use bevy_input::{ButtonState, keyboard::KeyboardInput};
+
+    fn composition(data: &str) -> CompositionEvent {
+        CompositionEvent {
+            data: data.to_string(),
+            ..default()
+        }
+    }
+
+    fn posted_ime(h: &Harness) -> Vec<WorkerToHost> {
+        h.bridge
+            .take_posted()
+            .into_iter()
+            .filter(|msg| matches!(msg, WorkerToHost::Ime { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn composition_to_preedit_and_commit() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(HostToWorker::KeyDown(key("KeyN", "n")));
+        assert_eq!(h.read::<KeyboardInput>().len(), 1);
+
+        h.bridge
+            .send(HostToWorker::CompositionStart(composition("")));
+        h.bridge
+            .send(HostToWorker::CompositionUpdate(composition("n")));
+        h.bridge.send(HostToWorker::KeyUp(key("KeyN", "n")));
+        h.bridge.send(HostToWorker::KeyDown(KeyboardEvent {
+            is_composing: true,
+            ..key("KeyI", "i")
+        }));
+        h.bridge
+            .send(HostToWorker::CompositionUpdate(composition("你")));
+        h.bridge.send(HostToWorker::KeyUp(KeyboardEvent {
+            is_composing: true,
+            ..key("KeyI", "i")
+        }));
+        h.bridge.send(HostToWorker::KeyDown(KeyboardEvent {
+            is_composing: true,
+            ..key("Space", " ")
+        }));
+        h.bridge
+            .send(HostToWorker::CompositionEnd(composition("你")));
+        h.send(HostToWorker::KeyUp(key("Space", " ")));
+
+        assert_eq!(
+            h.read::<Ime>(),
+            vec![
+                Ime::Preedit {
+                    window,
+                    value: String::new(),
+                    cursor: None
+                },
+                Ime::Preedit {
+                    window,
+                    value: "n".into(),
+                    cursor: Some((1, 1))
+                },
+                Ime::Preedit {
+                    window,
+                    value: "你".into(),
+                    cursor: Some((3, 3))
+                },
+                Ime::Preedit {
+                    window,
+                    value: String::new(),
+                    cursor: None
+                },
+                Ime::Commit {
+                    window,
+                    value: "你".into()
+                },
+            ]
+        );
+        // 组字前按下的键照常松开，组字中的按键都被吞掉
+        let keys: Vec<_> = h
+            .read::<KeyboardInput>()
+            .iter()
+            .map(|k| (k.key_code, k.state))
+            .collect();
+        assert_eq!(keys, vec![(KeyCode::KeyN, ButtonState::Released)]);
+        assert!(h.keys().get_pressed().next().is_none());
+
+        // 结束后恢复
+        h.send(HostToWorker::KeyDown(key("KeyA", "a")));
+        assert_eq!(h.read::<KeyboardInput>().len(), 1);
+    }
+
+    #[test]
+    fn blur_cancels_composition() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.bridge
+            .send(HostToWorker::CompositionStart(composition("")));
+        h.bridge
+            .send(HostToWorker::CompositionUpdate(composition("n")));
+        h.send(HostToWorker::KeyDown(KeyboardEvent {
+            is_composing: true,
+            ..key("KeyN", "n")
+        }));
+        h.read::<Ime>();
+
+        h.send(HostToWorker::Focus(false));
+        assert_eq!(
+            h.read::<Ime>(),
+            vec![Ime::Preedit {
+                window,
+                value: String::new(),
+                cursor: None
+            }]
+        );
+        assert!(h.read::<KeyboardInput>().is_empty());
+
+        // 没有 compositionend 也恢复按键
+        h.send(HostToWorker::Focus(true));
+        h.send(HostToWorker::KeyDown(key("KeyN", "n")));
+        h.send(HostToWorker::KeyUp(key("KeyN", "n")));
+        assert_eq!(h.read::<KeyboardInput>().len(), 2);
+
+        // 未在组字时失去焦点不产生预编辑
+        h.send(HostToWorker::Focus(false));
+        assert!(h.read::<Ime>().is_empty());
+    }
+
+    #[test]
+    fn process_key_is_swallowed() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::KeyDown(key("KeyA", "Process")));
+        h.send(HostToWorker::KeyUp(key("KeyA", "a")));
+        assert!(h.read::<KeyboardInput>().is_empty());
+    }
+
+    #[test]
+    fn posts_ime_state() {
+        let mut h = Harness::with_plugins(1.0, |app| {
+            app.add_plugins(ImePlugin);
+        });
+        let window = h.window;
+
+        h.frame();
+        assert_eq!(
+            posted_ime(&h),
+            vec![WorkerToHost::Ime {
+                enabled: false,
+                x: 0.,
+                y: 0.
+            }]
+        );
+        assert!(h.read::<Ime>().is_empty());
+
+        let mut w = h.world_mut().get_mut::<Window>(window).unwrap();
+        w.ime_enabled = true;
+        w.ime_position = Vec2::new(40., 60.);
+        h.frame();
+        assert_eq!(
+            posted_ime(&h),
+            vec![WorkerToHost::Ime {
+                enabled: true,
+                x: 40.,
+                y: 60.
+            }]
+        );
+        assert_eq!(h.read::<Ime>(), vec![Ime::Enabled { window }]);
+
+        h.frame();
+        assert!(posted_ime(&h).is_empty());
+
+        h.world_mut().get_mut::<Window>(window).unwrap().ime_enabled = false;
+        h.frame();
+        assert_eq!(posted_ime(&h).len(), 1);
+        assert_eq!(h.read::<Ime>(), vec![Ime::Disabled { window }]);
+    }
+}
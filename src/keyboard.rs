@@ -5,6 +5,7 @@
 
 use bevy_input::keyboard::{NativeKey, Key, NativeKeyCode, KeyCode};
 use smol_str::SmolStr;
+use unicode_segmentation::UnicodeSegmentation;
 
 pub trait AsKey {
     fn as_key(&self) -> Key;
@@ -320,110 +321,332 @@ impl AsKey for &str {
             "F33" => Key::F33,
             "F34" => Key::F34,
             "F35" => Key::F35,
-            "Backquote" => Key::Character(SmolStr::new("Backquote")),
-            "Backslash" => Key::Character(SmolStr::new("Backslash")),
-            "BracketLeft" => Key::Character(SmolStr::new("BracketLeft")),
-            "BracketRight" => Key::Character(SmolStr::new("BracketRight")),
-            "Comma" => Key::Character(SmolStr::new("Comma")),
-            "Digit0" => Key::Character(SmolStr::new("Digit0")),
-            "Digit1" => Key::Character(SmolStr::new("Digit1")),
-            "Digit2" => Key::Character(SmolStr::new("Digit2")),
-            "Digit3" => Key::Character(SmolStr::new("Digit3")),
-            "Digit4" => Key::Character(SmolStr::new("Digit4")),
-            "Digit5" => Key::Character(SmolStr::new("Digit5")),
-            "Digit6" => Key::Character(SmolStr::new("Digit6")),
-            "Digit7" => Key::Character(SmolStr::new("Digit7")),
-            "Digit8" => Key::Character(SmolStr::new("Digit8")),
-            "Digit9" => Key::Character(SmolStr::new("Digit9")),
-            "Equal" => Key::Character(SmolStr::new("Equal")),
-            "IntlBackslash" => Key::Character(SmolStr::new("IntlBackslash")),
-            "IntlRo" => Key::Character(SmolStr::new("IntlRo")),
-            "IntlYen" => Key::Character(SmolStr::new("IntlYen")),
-            "KeyA" => Key::Character(SmolStr::new("KeyA")),
-            "KeyB" => Key::Character(SmolStr::new("KeyB")),
-            "KeyC" => Key::Character(SmolStr::new("KeyC")),
-            "KeyD" => Key::Character(SmolStr::new("KeyD")),
-            "KeyE" => Key::Character(SmolStr::new("KeyE")),
-            "KeyF" => Key::Character(SmolStr::new("KeyF")),
-            "KeyG" => Key::Character(SmolStr::new("KeyG")),
-            "KeyH" => Key::Character(SmolStr::new("KeyH")),
-            "KeyI" => Key::Character(SmolStr::new("KeyI")),
-            "KeyJ" => Key::Character(SmolStr::new("KeyJ")),
-            "KeyK" => Key::Character(SmolStr::new("KeyK")),
-            "KeyL" => Key::Character(SmolStr::new("KeyL")),
-            "KeyM" => Key::Character(SmolStr::new("KeyM")),
-            "KeyN" => Key::Character(SmolStr::new("KeyN")),
-            "KeyO" => Key::Character(SmolStr::new("KeyO")),
-            "KeyP" => Key::Character(SmolStr::new("KeyP")),
-            "KeyQ" => Key::Character(SmolStr::new("KeyQ")),
-            "KeyR" => Key::Character(SmolStr::new("KeyR")),
-            "KeyS" => Key::Character(SmolStr::new("KeyS")),
-            "KeyT" => Key::Character(SmolStr::new("KeyT")),
-            "KeyU" => Key::Character(SmolStr::new("KeyU")),
-            "KeyV" => Key::Character(SmolStr::new("KeyV")),
-            "KeyW" => Key::Character(SmolStr::new("KeyW")),
-            "KeyX" => Key::Character(SmolStr::new("KeyX")),
-            "KeyY" => Key::Character(SmolStr::new("KeyY")),
-            "KeyZ" => Key::Character(SmolStr::new("KeyZ")),
-            "Minus" => Key::Character(SmolStr::new("Minus")),
-            "Period" => Key::Character(SmolStr::new("Period")),
-            "Quote" => Key::Character(SmolStr::new("Quote")),
-            "Semicolon" => Key::Character(SmolStr::new("Semicolon")),
-            "Slash" => Key::Character(SmolStr::new("Slash")),
-            "AltLeft" => Key::Character(SmolStr::new("AltLeft")),
-            "AltRight" => Key::Character(SmolStr::new("AltRight")),
-            "ControlLeft" => Key::Character(SmolStr::new("ControlLeft")),
-            "ControlRight" => Key::Character(SmolStr::new("ControlRight")),
-            "MetaLeft" | "OSLeft" => Key::Character(SmolStr::new("MetaLeft")),
-            "MetaRight" | "OSRight" => Key::Character(SmolStr::new("MetaRight")),
-            "ShiftLeft" => Key::Character(SmolStr::new("ShiftLeft")),
-            "ShiftRight" => Key::Character(SmolStr::new("ShiftRight")),
-            "Space" => Key::Character(SmolStr::new("Space")),
-            "Lang1" => Key::Character(SmolStr::new("Lang1")),
-            "Lang2" => Key::Character(SmolStr::new("Lang2")),
-            "Lang3" => Key::Character(SmolStr::new("Lang3")),
-            "Lang4" => Key::Character(SmolStr::new("Lang4")),
-            "Lang5" => Key::Character(SmolStr::new("Lang5")),
-            "Numpad0" => Key::Character(SmolStr::new("Numpad0")),
-            "Numpad1" => Key::Character(SmolStr::new("Numpad1")),
-            "Numpad2" => Key::Character(SmolStr::new("Numpad2")),
-            "Numpad3" => Key::Character(SmolStr::new("Numpad3")),
-            "Numpad4" => Key::Character(SmolStr::new("Numpad4")),
-            "Numpad5" => Key::Character(SmolStr::new("Numpad5")),
-            "Numpad6" => Key::Character(SmolStr::new("Numpad6")),
-            "Numpad7" => Key::Character(SmolStr::new("Numpad7")),
-            "Numpad8" => Key::Character(SmolStr::new("Numpad8")),
-            "Numpad9" => Key::Character(SmolStr::new("Numpad9")),
-            "NumpadAdd" => Key::Character(SmolStr::new("NumpadAdd")),
-            "NumpadBackspace" => Key::Character(SmolStr::new("NumpadBackspace")),
-            "NumpadClear" => Key::Character(SmolStr::new("NumpadClear")),
-            "NumpadClearEntry" => Key::Character(SmolStr::new("NumpadClearEntry")),
-            "NumpadComma" => Key::Character(SmolStr::new("NumpadComma")),
-            "NumpadDecimal" => Key::Character(SmolStr::new("NumpadDecimal")),
-            "NumpadDivide" => Key::Character(SmolStr::new("NumpadDivide")),
-            "NumpadEnter" => Key::Character(SmolStr::new("NumpadEnter")),
-            "NumpadEqual" => Key::Character(SmolStr::new("NumpadEqual")),
-            "NumpadHash" => Key::Character(SmolStr::new("NumpadHash")),
-            "NumpadMemoryAdd" => Key::Character(SmolStr::new("NumpadMemoryAdd")),
-            "NumpadMemoryClear" => Key::Character(SmolStr::new("NumpadMemoryClear")),
-            "NumpadMemoryRecall" => Key::Character(SmolStr::new("NumpadMemoryRecall")),
-            "NumpadMemoryStore" => Key::Character(SmolStr::new("NumpadMemoryStore")),
-            "NumpadMemorySubtract" => Key::Character(SmolStr::new("NumpadMemorySubtract")),
-            "NumpadMultiply" => Key::Character(SmolStr::new("NumpadMultiply")),
-            "NumpadParenLeft" => Key::Character(SmolStr::new("NumpadParenLeft")),
-            "NumpadParenRight" => Key::Character(SmolStr::new("NumpadParenRight")),
-            "NumpadStar" => Key::Character(SmolStr::new("NumpadStar")),
-            "NumpadSubtract" => Key::Character(SmolStr::new("NumpadSubtract")),
-            "LaunchApp1" => Key::Character(SmolStr::new("LaunchApp1")),
-            "LaunchApp2" => Key::Character(SmolStr::new("LaunchApp2")),
-            "MediaSelect" | "LaunchMediaPlayer" => Key::Character(SmolStr::new("MediaSelect")),
-            "Sleep" => Key::Character(SmolStr::new("Sleep")),
-            "Turbo" => Key::Character(SmolStr::new("Turbo")),
-            "Abort" => Key::Character(SmolStr::new("Abort")),
-            "Resume" => Key::Character(SmolStr::new("Resume")),
-            "Suspend" => Key::Character(SmolStr::new("Suspend")),
 
-            _ => todo!(),
+            // 非命名键的 key 值是按键产生的文本
+            key if key.graphemes(true).count() == 1 => Key::Character(SmolStr::new(key)),
+            key => Key::Unidentified(NativeKey::Web(SmolStr::new(key))),
+        }
+    }
+}
+
+/// [`AsKey`] 的逆转换，得到 `KeyboardEvent.key` 的值
+pub trait ToW3cKey {
+    fn to_w3c_key(&self) -> &str;
+}
+
+impl ToW3cKey for Key {
+    fn to_w3c_key(&self) -> &str {
+        match self {
+            Key::Character(text) => text,
+            Key::Space => " ",
+            Key::Unidentified(NativeKey::Web(key)) => key,
+            Key::Alt => "Alt",
+            Key::AltGraph => "AltGraph",
+            Key::CapsLock => "CapsLock",
+            Key::Control => "Control",
+            Key::Fn => "Fn",
+            Key::FnLock => "FnLock",
+            Key::Meta => "Meta",
+            Key::NumLock => "NumLock",
+            Key::ScrollLock => "ScrollLock",
+            Key::Shift => "Shift",
+            Key::Symbol => "Symbol",
+            Key::SymbolLock => "SymbolLock",
+            Key::Hyper => "Hyper",
+            Key::Super => "Super",
+            Key::Enter => "Enter",
+            Key::Tab => "Tab",
+            Key::ArrowDown => "ArrowDown",
+            Key::ArrowLeft => "ArrowLeft",
+            Key::ArrowRight => "ArrowRight",
+            Key::ArrowUp => "ArrowUp",
+            Key::End => "End",
+            Key::Home => "Home",
+            Key::PageDown => "PageDown",
+            Key::PageUp => "PageUp",
+            Key::Backspace => "Backspace",
+            Key::Clear => "Clear",
+            Key::Copy => "Copy",
+            Key::CrSel => "CrSel",
+            Key::Cut => "Cut",
+            Key::Delete => "Delete",
+            Key::EraseEof => "EraseEof",
+            Key::ExSel => "ExSel",
+            Key::Insert => "Insert",
+            Key::Paste => "Paste",
+            Key::Redo => "Redo",
+            Key::Undo => "Undo",
+            Key::Accept => "Accept",
+            Key::Again => "Again",
+            Key::Attn => "Attn",
+            Key::Cancel => "Cancel",
+            Key::ContextMenu => "ContextMenu",
+            Key::Escape => "Escape",
+            Key::Execute => "Execute",
+            Key::Find => "Find",
+            Key::Help => "Help",
+            Key::Pause => "Pause",
+            Key::Play => "Play",
+            Key::Props => "Props",
+            Key::Select => "Select",
+            Key::ZoomIn => "ZoomIn",
+            Key::ZoomOut => "ZoomOut",
+            Key::BrightnessDown => "BrightnessDown",
+            Key::BrightnessUp => "BrightnessUp",
+            Key::Eject => "Eject",
+            Key::LogOff => "LogOff",
+            Key::Power => "Power",
+            Key::PowerOff => "PowerOff",
+            Key::PrintScreen => "PrintScreen",
+            Key::Hibernate => "Hibernate",
+            Key::Standby => "Standby",
+            Key::WakeUp => "WakeUp",
+            Key::AllCandidates => "AllCandidates",
+            Key::Alphanumeric => "Alphanumeric",
+            Key::CodeInput => "CodeInput",
+            Key::Compose => "Compose",
+            Key::Convert => "Convert",
+            Key::Dead(_) => "Dead",
+            Key::FinalMode => "FinalMode",
+            Key::GroupFirst => "GroupFirst",
+            Key::GroupLast => "GroupLast",
+            Key::GroupNext => "GroupNext",
+            Key::GroupPrevious => "GroupPrevious",
+            Key::ModeChange => "ModeChange",
+            Key::NextCandidate => "NextCandidate",
+            Key::NonConvert => "NonConvert",
+            Key::PreviousCandidate => "PreviousCandidate",
+            Key::Process => "Process",
+            Key::SingleCandidate => "SingleCandidate",
+            Key::HangulMode => "HangulMode",
+            Key::HanjaMode => "HanjaMode",
+            Key::JunjaMode => "JunjaMode",
+            Key::Eisu => "Eisu",
+            Key::Hankaku => "Hankaku",
+            Key::Hiragana => "Hiragana",
+            Key::HiraganaKatakana => "HiraganaKatakana",
+            Key::KanaMode => "KanaMode",
+            Key::KanjiMode => "KanjiMode",
+            Key::Katakana => "Katakana",
+            Key::Romaji => "Romaji",
+            Key::Zenkaku => "Zenkaku",
+            Key::ZenkakuHankaku => "ZenkakuHankaku",
+            Key::Soft1 => "Soft1",
+            Key::Soft2 => "Soft2",
+            Key::Soft3 => "Soft3",
+            Key::Soft4 => "Soft4",
+            Key::ChannelDown => "ChannelDown",
+            Key::ChannelUp => "ChannelUp",
+            Key::Close => "Close",
+            Key::MailForward => "MailForward",
+            Key::MailReply => "MailReply",
+            Key::MailSend => "MailSend",
+            Key::MediaClose => "MediaClose",
+            Key::MediaFastForward => "MediaFastForward",
+            Key::MediaPause => "MediaPause",
+            Key::MediaPlay => "MediaPlay",
+            Key::MediaPlayPause => "MediaPlayPause",
+            Key::MediaRecord => "MediaRecord",
+            Key::MediaRewind => "MediaRewind",
+            Key::MediaStop => "MediaStop",
+            Key::MediaTrackNext => "MediaTrackNext",
+            Key::MediaTrackPrevious => "MediaTrackPrevious",
+            Key::New => "New",
+            Key::Open => "Open",
+            Key::Print => "Print",
+            Key::Save => "Save",
+            Key::SpellCheck => "SpellCheck",
+            Key::Key11 => "Key11",
+            Key::Key12 => "Key12",
+            Key::AudioBalanceLeft => "AudioBalanceLeft",
+            Key::AudioBalanceRight => "AudioBalanceRight",
+            Key::AudioBassBoostDown => "AudioBassBoostDown",
+            Key::AudioBassBoostToggle => "AudioBassBoostToggle",
+            Key::AudioBassBoostUp => "AudioBassBoostUp",
+            Key::AudioFaderFront => "AudioFaderFront",
+            Key::AudioFaderRear => "AudioFaderRear",
+            Key::AudioSurroundModeNext => "AudioSurroundModeNext",
+            Key::AudioTrebleDown => "AudioTrebleDown",
+            Key::AudioTrebleUp => "AudioTrebleUp",
+            Key::AudioVolumeDown => "AudioVolumeDown",
+            Key::AudioVolumeUp => "AudioVolumeUp",
+            Key::AudioVolumeMute => "AudioVolumeMute",
+            Key::MicrophoneToggle => "MicrophoneToggle",
+            Key::MicrophoneVolumeDown => "MicrophoneVolumeDown",
+            Key::MicrophoneVolumeUp => "MicrophoneVolumeUp",
+            Key::MicrophoneVolumeMute => "MicrophoneVolumeMute",
+            Key::SpeechCorrectionList => "SpeechCorrectionList",
+            Key::SpeechInputToggle => "SpeechInputToggle",
+            Key::LaunchApplication1 => "LaunchApplication1",
+            Key::LaunchApplication2 => "LaunchApplication2",
+            Key::LaunchCalendar => "LaunchCalendar",
+            Key::LaunchContacts => "LaunchContacts",
+            Key::LaunchMail => "LaunchMail",
+            Key::LaunchMediaPlayer => "LaunchMediaPlayer",
+            Key::LaunchMusicPlayer => "LaunchMusicPlayer",
+            Key::LaunchPhone => "LaunchPhone",
+            Key::LaunchScreenSaver => "LaunchScreenSaver",
+            Key::LaunchSpreadsheet => "LaunchSpreadsheet",
+            Key::LaunchWebBrowser => "LaunchWebBrowser",
+            Key::LaunchWebCam => "LaunchWebCam",
+            Key::LaunchWordProcessor => "LaunchWordProcessor",
+            Key::BrowserBack => "BrowserBack",
+            Key::BrowserFavorites => "BrowserFavorites",
+            Key::BrowserForward => "BrowserForward",
+            Key::BrowserHome => "BrowserHome",
+            Key::BrowserRefresh => "BrowserRefresh",
+            Key::BrowserSearch => "BrowserSearch",
+            Key::BrowserStop => "BrowserStop",
+            Key::AppSwitch => "AppSwitch",
+            Key::Call => "Call",
+            Key::Camera => "Camera",
+            Key::CameraFocus => "CameraFocus",
+            Key::EndCall => "EndCall",
+            Key::GoBack => "GoBack",
+            Key::GoHome => "GoHome",
+            Key::HeadsetHook => "HeadsetHook",
+            Key::LastNumberRedial => "LastNumberRedial",
+            Key::Notification => "Notification",
+            Key::MannerMode => "MannerMode",
+            Key::VoiceDial => "VoiceDial",
+            Key::TV => "TV",
+            Key::TV3DMode => "TV3DMode",
+            Key::TVAntennaCable => "TVAntennaCable",
+            Key::TVAudioDescription => "TVAudioDescription",
+            Key::TVAudioDescriptionMixDown => "TVAudioDescriptionMixDown",
+            Key::TVAudioDescriptionMixUp => "TVAudioDescriptionMixUp",
+            Key::TVContentsMenu => "TVContentsMenu",
+            Key::TVDataService => "TVDataService",
+            Key::TVInput => "TVInput",
+            Key::TVInputComponent1 => "TVInputComponent1",
+            Key::TVInputComponent2 => "TVInputComponent2",
+            Key::TVInputComposite1 => "TVInputComposite1",
+            Key::TVInputComposite2 => "TVInputComposite2",
+            Key::TVInputHDMI1 => "TVInputHDMI1",
+            Key::TVInputHDMI2 => "TVInputHDMI2",
+            Key::TVInputHDMI3 => "TVInputHDMI3",
+            Key::TVInputHDMI4 => "TVInputHDMI4",
+            Key::TVInputVGA1 => "TVInputVGA1",
+            Key::TVMediaContext => "TVMediaContext",
+            Key::TVNetwork => "TVNetwork",
+            Key::TVNumberEntry => "TVNumberEntry",
+            Key::TVPower => "TVPower",
+            Key::TVRadioService => "TVRadioService",
+            Key::TVSatellite => "TVSatellite",
+            Key::TVSatelliteBS => "TVSatelliteBS",
+            Key::TVSatelliteCS => "TVSatelliteCS",
+            Key::TVSatelliteToggle => "TVSatelliteToggle",
+            Key::TVTerrestrialAnalog => "TVTerrestrialAnalog",
+            Key::TVTerrestrialDigital => "TVTerrestrialDigital",
+            Key::TVTimer => "TVTimer",
+            Key::AVRInput => "AVRInput",
+            Key::AVRPower => "AVRPower",
+            Key::ColorF0Red => "ColorF0Red",
+            Key::ColorF1Green => "ColorF1Green",
+            Key::ColorF2Yellow => "ColorF2Yellow",
+            Key::ColorF3Blue => "ColorF3Blue",
+            Key::ColorF4Grey => "ColorF4Grey",
+            Key::ColorF5Brown => "ColorF5Brown",
+            Key::ClosedCaptionToggle => "ClosedCaptionToggle",
+            Key::Dimmer => "Dimmer",
+            Key::DisplaySwap => "DisplaySwap",
+            Key::DVR => "DVR",
+            Key::Exit => "Exit",
+            Key::FavoriteClear0 => "FavoriteClear0",
+            Key::FavoriteClear1 => "FavoriteClear1",
+            Key::FavoriteClear2 => "FavoriteClear2",
+            Key::FavoriteClear3 => "FavoriteClear3",
+            Key::FavoriteRecall0 => "FavoriteRecall0",
+            Key::FavoriteRecall1 => "FavoriteRecall1",
+            Key::FavoriteRecall2 => "FavoriteRecall2",
+            Key::FavoriteRecall3 => "FavoriteRecall3",
+            Key::FavoriteStore0 => "FavoriteStore0",
+            Key::FavoriteStore1 => "FavoriteStore1",
+            Key::FavoriteStore2 => "FavoriteStore2",
+            Key::FavoriteStore3 => "FavoriteStore3",
+            Key::Guide => "Guide",
+            Key::GuideNextDay => "GuideNextDay",
+            Key::GuidePreviousDay => "GuidePreviousDay",
+            Key::Info => "Info",
+            Key::InstantReplay => "InstantReplay",
+            Key::Link => "Link",
+            Key::ListProgram => "ListProgram",
+            Key::LiveContent => "LiveContent",
+            Key::Lock => "Lock",
+            Key::MediaApps => "MediaApps",
+            Key::MediaAudioTrack => "MediaAudioTrack",
+            Key::MediaLast => "MediaLast",
+            Key::MediaSkipBackward => "MediaSkipBackward",
+            Key::MediaSkipForward => "MediaSkipForward",
+            Key::MediaStepBackward => "MediaStepBackward",
+            Key::MediaStepForward => "MediaStepForward",
+            Key::MediaTopMenu => "MediaTopMenu",
+            Key::NavigateIn => "NavigateIn",
+            Key::NavigateNext => "NavigateNext",
+            Key::NavigateOut => "NavigateOut",
+            Key::NavigatePrevious => "NavigatePrevious",
+            Key::NextFavoriteChannel => "NextFavoriteChannel",
+            Key::NextUserProfile => "NextUserProfile",
+            Key::OnDemand => "OnDemand",
+            Key::Pairing => "Pairing",
+            Key::PinPDown => "PinPDown",
+            Key::PinPMove => "PinPMove",
+            Key::PinPToggle => "PinPToggle",
+            Key::PinPUp => "PinPUp",
+            Key::PlaySpeedDown => "PlaySpeedDown",
+            Key::PlaySpeedReset => "PlaySpeedReset",
+            Key::PlaySpeedUp => "PlaySpeedUp",
+            Key::RandomToggle => "RandomToggle",
+            Key::RcLowBattery => "RcLowBattery",
+            Key::RecordSpeedNext => "RecordSpeedNext",
+            Key::RfBypass => "RfBypass",
+            Key::ScanChannelsToggle => "ScanChannelsToggle",
+            Key::ScreenModeNext => "ScreenModeNext",
+            Key::Settings => "Settings",
+            Key::SplitScreenToggle => "SplitScreenToggle",
+            Key::STBInput => "STBInput",
+            Key::STBPower => "STBPower",
+            Key::Subtitle => "Subtitle",
+            Key::Teletext => "Teletext",
+            Key::VideoModeNext => "VideoModeNext",
+            Key::Wink => "Wink",
+            Key::ZoomToggle => "ZoomToggle",
+            Key::F1 => "F1",
+            Key::F2 => "F2",
+            Key::F3 => "F3",
+            Key::F4 => "F4",
+            Key::F5 => "F5",
+            Key::F6 => "F6",
+            Key::F7 => "F7",
+            Key::F8 => "F8",
+            Key::F9 => "F9",
+            Key::F10 => "F10",
+            Key::F11 => "F11",
+            Key::F12 => "F12",
+            Key::F13 => "F13",
+            Key::F14 => "F14",
+            Key::F15 => "F15",
+            Key::F16 => "F16",
+            Key::F17 => "F17",
+            Key::F18 => "F18",
+            Key::F19 => "F19",
+            Key::F20 => "F20",
+            Key::F21 => "F21",
+            Key::F22 => "F22",
+            Key::F23 => "F23",
+            Key::F24 => "F24",
+            Key::F25 => "F25",
+            Key::F26 => "F26",
+            Key::F27 => "F27",
+            Key::F28 => "F28",
+            Key::F29 => "F29",
+            Key::F30 => "F30",
+            Key::F31 => "F31",
+            Key::F32 => "F32",
+            Key::F33 => "F33",
+            Key::F34 => "F34",
+            Key::F35 => "F35",
+            _ => "Unidentified",
         }
     }
 } 
@@ -492,8 +715,8 @@ impl AsKeyCode for &str {
             "ControlLeft" => KeyCode::ControlLeft,
             "ControlRight" => KeyCode::ControlRight,
             "Enter" => KeyCode::Enter,
-            "Meta" | "OSLeft" => KeyCode::Meta,
-            "Meta" | "OSRight" => KeyCode::Meta,
+            "MetaLeft" | "OSLeft" => KeyCode::SuperLeft,
+            "MetaRight" | "OSRight" => KeyCode::SuperRight,
             "ShiftLeft" => KeyCode::ShiftLeft,
             "ShiftRight" => KeyCode::ShiftRight,
             "Space" => KeyCode::Space,
@@ -577,7 +800,7 @@ impl AsKeyCode for &str {
             "AudioVolumeUp" | "VolumeUp" => KeyCode::AudioVolumeUp,
             "WakeUp" => KeyCode::WakeUp,
             "Hyper" => KeyCode::Hyper,
-            "Meta" => KeyCode::Meta,
+            "Super" => KeyCode::Meta,
             "Turbo" => KeyCode::Turbo,
             "Abort" => KeyCode::Abort,
             "Resume" => KeyCode::Resume,
@@ -632,4 +855,778 @@ impl AsKeyCode for &str {
             _ => KeyCode::Unidentified(NativeKeyCode::Unidentified),
         }
     }
-}
\ No newline at end of file
+}
+
+/// [`AsKeyCode`] 的逆转换，得到 `KeyboardEvent.code` 的值
+pub trait ToW3cCode {
+    fn to_w3c_code(&self) -> &'static str;
+}
+
+impl ToW3cCode for KeyCode {
+    fn to_w3c_code(&self) -> &'static str {
+        match self {
+            KeyCode::Backquote => "Backquote",
+            KeyCode::Backslash => "Backslash",
+            KeyCode::BracketLeft => "BracketLeft",
+            KeyCode::BracketRight => "BracketRight",
+            KeyCode::Comma => "Comma",
+            KeyCode::Digit0 => "Digit0",
+            KeyCode::Digit1 => "Digit1",
+            KeyCode::Digit2 => "Digit2",
+            KeyCode::Digit3 => "Digit3",
+            KeyCode::Digit4 => "Digit4",
+            KeyCode::Digit5 => "Digit5",
+            KeyCode::Digit6 => "Digit6",
+            KeyCode::Digit7 => "Digit7",
+            KeyCode::Digit8 => "Digit8",
+            KeyCode::Digit9 => "Digit9",
+            KeyCode::Equal => "Equal",
+            KeyCode::IntlBackslash => "IntlBackslash",
+            KeyCode::IntlRo => "IntlRo",
+            KeyCode::IntlYen => "IntlYen",
+            KeyCode::KeyA => "KeyA",
+            KeyCode::KeyB => "KeyB",
+            KeyCode::KeyC => "KeyC",
+            KeyCode::KeyD => "KeyD",
+            KeyCode::KeyE => "KeyE",
+            KeyCode::KeyF => "KeyF",
+            KeyCode::KeyG => "KeyG",
+            KeyCode::KeyH => "KeyH",
+            KeyCode::KeyI => "KeyI",
+            KeyCode::KeyJ => "KeyJ",
+            KeyCode::KeyK => "KeyK",
+            KeyCode::KeyL => "KeyL",
+            KeyCode::KeyM => "KeyM",
+            KeyCode::KeyN => "KeyN",
+            KeyCode::KeyO => "KeyO",
+            KeyCode::KeyP => "KeyP",
+            KeyCode::KeyQ => "KeyQ",
+            KeyCode::KeyR => "KeyR",
+            KeyCode::KeyS => "KeyS",
+            KeyCode::KeyT => "KeyT",
+            KeyCode::KeyU => "KeyU",
+            KeyCode::KeyV => "KeyV",
+            KeyCode::KeyW => "KeyW",
+            KeyCode::KeyX => "KeyX",
+            KeyCode::KeyY => "KeyY",
+            KeyCode::KeyZ => "KeyZ",
+            KeyCode::Minus => "Minus",
+            KeyCode::Period => "Period",
+            KeyCode::Quote => "Quote",
+            KeyCode::Semicolon => "Semicolon",
+            KeyCode::Slash => "Slash",
+            KeyCode::AltLeft => "AltLeft",
+            KeyCode::AltRight => "AltRight",
+            KeyCode::Backspace => "Backspace",
+            KeyCode::CapsLock => "CapsLock",
+            KeyCode::ContextMenu => "ContextMenu",
+            KeyCode::ControlLeft => "ControlLeft",
+            KeyCode::ControlRight => "ControlRight",
+            KeyCode::Enter => "Enter",
+            KeyCode::SuperLeft => "MetaLeft",
+            KeyCode::SuperRight => "MetaRight",
+            KeyCode::ShiftLeft => "ShiftLeft",
+            KeyCode::ShiftRight => "ShiftRight",
+            KeyCode::Space => "Space",
+            KeyCode::Tab => "Tab",
+            KeyCode::Convert => "Convert",
+            KeyCode::KanaMode => "KanaMode",
+            KeyCode::Lang1 => "Lang1",
+            KeyCode::Lang2 => "Lang2",
+            KeyCode::Lang3 => "Lang3",
+            KeyCode::Lang4 => "Lang4",
+            KeyCode::Lang5 => "Lang5",
+            KeyCode::NonConvert => "NonConvert",
+            KeyCode::Delete => "Delete",
+            KeyCode::End => "End",
+            KeyCode::Help => "Help",
+            KeyCode::Home => "Home",
+            KeyCode::Insert => "Insert",
+            KeyCode::PageDown => "PageDown",
+            KeyCode::PageUp => "PageUp",
+            KeyCode::ArrowDown => "ArrowDown",
+            KeyCode::ArrowLeft => "ArrowLeft",
+            KeyCode::ArrowRight => "ArrowRight",
+            KeyCode::ArrowUp => "ArrowUp",
+            KeyCode::NumLock => "NumLock",
+            KeyCode::Numpad0 => "Numpad0",
+            KeyCode::Numpad1 => "Numpad1",
+            KeyCode::Numpad2 => "Numpad2",
+            KeyCode::Numpad3 => "Numpad3",
+            KeyCode::Numpad4 => "Numpad4",
+            KeyCode::Numpad5 => "Numpad5",
+            KeyCode::Numpad6 => "Numpad6",
+            KeyCode::Numpad7 => "Numpad7",
+            KeyCode::Numpad8 => "Numpad8",
+            KeyCode::Numpad9 => "Numpad9",
+            KeyCode::NumpadAdd => "NumpadAdd",
+            KeyCode::NumpadBackspace => "NumpadBackspace",
+            KeyCode::NumpadClear => "NumpadClear",
+            KeyCode::NumpadClearEntry => "NumpadClearEntry",
+            KeyCode::NumpadComma => "NumpadComma",
+            KeyCode::NumpadDecimal => "NumpadDecimal",
+            KeyCode::NumpadDivide => "NumpadDivide",
+            KeyCode::NumpadEnter => "NumpadEnter",
+            KeyCode::NumpadEqual => "NumpadEqual",
+            KeyCode::NumpadHash => "NumpadHash",
+            KeyCode::NumpadMemoryAdd => "NumpadMemoryAdd",
+            KeyCode::NumpadMemoryClear => "NumpadMemoryClear",
+            KeyCode::NumpadMemoryRecall => "NumpadMemoryRecall",
+            KeyCode::NumpadMemoryStore => "NumpadMemoryStore",
+            KeyCode::NumpadMemorySubtract => "NumpadMemorySubtract",
+            KeyCode::NumpadMultiply => "NumpadMultiply",
+            KeyCode::NumpadParenLeft => "NumpadParenLeft",
+            KeyCode::NumpadParenRight => "NumpadParenRight",
+            KeyCode::NumpadStar => "NumpadStar",
+            KeyCode::NumpadSubtract => "NumpadSubtract",
+            KeyCode::Escape => "Escape",
+            KeyCode::Fn => "Fn",
+            KeyCode::FnLock => "FnLock",
+            KeyCode::PrintScreen => "PrintScreen",
+            KeyCode::ScrollLock => "ScrollLock",
+            KeyCode::Pause => "Pause",
+            KeyCode::BrowserBack => "BrowserBack",
+            KeyCode::BrowserFavorites => "BrowserFavorites",
+            KeyCode::BrowserForward => "BrowserForward",
+            KeyCode::BrowserHome => "BrowserHome",
+            KeyCode::BrowserRefresh => "BrowserRefresh",
+            KeyCode::BrowserSearch => "BrowserSearch",
+            KeyCode::BrowserStop => "BrowserStop",
+            KeyCode::Eject => "Eject",
+            KeyCode::LaunchApp1 => "LaunchApp1",
+            KeyCode::LaunchApp2 => "LaunchApp2",
+            KeyCode::LaunchMail => "LaunchMail",
+            KeyCode::MediaPlayPause => "MediaPlayPause",
+            KeyCode::MediaSelect => "MediaSelect",
+            KeyCode::MediaStop => "MediaStop",
+            KeyCode::MediaTrackNext => "MediaTrackNext",
+            KeyCode::MediaTrackPrevious => "MediaTrackPrevious",
+            KeyCode::Power => "Power",
+            KeyCode::Sleep => "Sleep",
+            KeyCode::AudioVolumeDown => "AudioVolumeDown",
+            KeyCode::AudioVolumeMute => "AudioVolumeMute",
+            KeyCode::AudioVolumeUp => "AudioVolumeUp",
+            KeyCode::WakeUp => "WakeUp",
+            KeyCode::Hyper => "Hyper",
+            KeyCode::Meta => "Super",
+            KeyCode::Turbo => "Turbo",
+            KeyCode::Abort => "Abort",
+            KeyCode::Resume => "Resume",
+            KeyCode::Suspend => "Suspend",
+            KeyCode::Again => "Again",
+            KeyCode::Copy => "Copy",
+            KeyCode::Cut => "Cut",
+            KeyCode::Find => "Find",
+            KeyCode::Open => "Open",
+            KeyCode::Paste => "Paste",
+            KeyCode::Props => "Props",
+            KeyCode::Select => "Select",
+            KeyCode::Undo => "Undo",
+            KeyCode::Hiragana => "Hiragana",
+            KeyCode::Katakana => "Katakana",
+            KeyCode::F1 => "F1",
+            KeyCode::F2 => "F2",
+            KeyCode::F3 => "F3",
+            KeyCode::F4 => "F4",
+            KeyCode::F5 => "F5",
+            KeyCode::F6 => "F6",
+            KeyCode::F7 => "F7",
+            KeyCode::F8 => "F8",
+            KeyCode::F9 => "F9",
+            KeyCode::F10 => "F10",
+            KeyCode::F11 => "F11",
+            KeyCode::F12 => "F12",
+            KeyCode::F13 => "F13",
+            KeyCode::F14 => "F14",
+            KeyCode::F15 => "F15",
+            KeyCode::F16 => "F16",
+            KeyCode::F17 => "F17",
+            KeyCode::F18 => "F18",
+            KeyCode::F19 => "F19",
+            KeyCode::F20 => "F20",
+            KeyCode::F21 => "F21",
+            KeyCode::F22 => "F22",
+            KeyCode::F23 => "F23",
+            KeyCode::F24 => "F24",
+            KeyCode::F25 => "F25",
+            KeyCode::F26 => "F26",
+            KeyCode::F27 => "F27",
+            KeyCode::F28 => "F28",
+            KeyCode::F29 => "F29",
+            KeyCode::F30 => "F30",
+            KeyCode::F31 => "F31",
+            KeyCode::F32 => "F32",
+            KeyCode::F33 => "F33",
+            KeyCode::F34 => "F34",
+            KeyCode::F35 => "F35",
+            KeyCode::Unidentified(_) => "Unidentified",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEYS: &[&str] = &[
+        "Unidentified",
+        "Alt",
+        "AltGraph",
+        "CapsLock",
+        "Control",
+        "Fn",
+        "FnLock",
+        "Meta",
+        "NumLock",
+        "ScrollLock",
+        "Shift",
+        "Symbol",
+        "SymbolLock",
+        "Hyper",
+        "Super",
+        "Enter",
+        "Tab",
+        "ArrowDown",
+        "ArrowLeft",
+        "ArrowRight",
+        "ArrowUp",
+        "End",
+        "Home",
+        "PageDown",
+        "PageUp",
+        "Backspace",
+        "Clear",
+        "Copy",
+        "CrSel",
+        "Cut",
+        "Delete",
+        "EraseEof",
+        "ExSel",
+        "Insert",
+        "Paste",
+        "Redo",
+        "Undo",
+        "Accept",
+        "Again",
+        "Attn",
+        "Cancel",
+        "ContextMenu",
+        "Escape",
+        "Execute",
+        "Find",
+        "Help",
+        "Pause",
+        "Play",
+        "Props",
+        "Select",
+        "ZoomIn",
+        "ZoomOut",
+        "BrightnessDown",
+        "BrightnessUp",
+        "Eject",
+        "LogOff",
+        "Power",
+        "PowerOff",
+        "PrintScreen",
+        "Hibernate",
+        "Standby",
+        "WakeUp",
+        "AllCandidates",
+        "Alphanumeric",
+        "CodeInput",
+        "Compose",
+        "Convert",
+        "Dead",
+        "FinalMode",
+        "GroupFirst",
+        "GroupLast",
+        "GroupNext",
+        "GroupPrevious",
+        "ModeChange",
+        "NextCandidate",
+        "NonConvert",
+        "PreviousCandidate",
+        "Process",
+        "SingleCandidate",
+        "HangulMode",
+        "HanjaMode",
+        "JunjaMode",
+        "Eisu",
+        "Hankaku",
+        "Hiragana",
+        "HiraganaKatakana",
+        "KanaMode",
+        "KanjiMode",
+        "Katakana",
+        "Romaji",
+        "Zenkaku",
+        "ZenkakuHankaku",
+        "Soft1",
+        "Soft2",
+        "Soft3",
+        "Soft4",
+        "ChannelDown",
+        "ChannelUp",
+        "Close",
+        "MailForward",
+        "MailReply",
+        "MailSend",
+        "MediaClose",
+        "MediaFastForward",
+        "MediaPause",
+        "MediaPlay",
+        "MediaPlayPause",
+        "MediaRecord",
+        "MediaRewind",
+        "MediaStop",
+        "MediaTrackNext",
+        "MediaTrackPrevious",
+        "New",
+        "Open",
+        "Print",
+        "Save",
+        "SpellCheck",
+        "Key11",
+        "Key12",
+        "AudioBalanceLeft",
+        "AudioBalanceRight",
+        "AudioBassBoostDown",
+        "AudioBassBoostToggle",
+        "AudioBassBoostUp",
+        "AudioFaderFront",
+        "AudioFaderRear",
+        "AudioSurroundModeNext",
+        "AudioTrebleDown",
+        "AudioTrebleUp",
+        "AudioVolumeDown",
+        "AudioVolumeUp",
+        "AudioVolumeMute",
+        "MicrophoneToggle",
+        "MicrophoneVolumeDown",
+        "MicrophoneVolumeUp",
+        "MicrophoneVolumeMute",
+        "SpeechCorrectionList",
+        "SpeechInputToggle",
+        "LaunchApplication1",
+        "LaunchApplication2",
+        "LaunchCalendar",
+        "LaunchContacts",
+        "LaunchMail",
+        "LaunchMediaPlayer",
+        "LaunchMusicPlayer",
+        "LaunchPhone",
+        "LaunchScreenSaver",
+        "LaunchSpreadsheet",
+        "LaunchWebBrowser",
+        "LaunchWebCam",
+        "LaunchWordProcessor",
+        "BrowserBack",
+        "BrowserFavorites",
+        "BrowserForward",
+        "BrowserHome",
+        "BrowserRefresh",
+        "BrowserSearch",
+        "BrowserStop",
+        "AppSwitch",
+        "Call",
+        "Camera",
+        "CameraFocus",
+        "EndCall",
+        "GoBack",
+        "GoHome",
+        "HeadsetHook",
+        "LastNumberRedial",
+        "Notification",
+        "MannerMode",
+        "VoiceDial",
+        "TV",
+        "TV3DMode",
+        "TVAntennaCable",
+        "TVAudioDescription",
+        "TVAudioDescriptionMixDown",
+        "TVAudioDescriptionMixUp",
+        "TVContentsMenu",
+        "TVDataService",
+        "TVInput",
+        "TVInputComponent1",
+        "TVInputComponent2",
+        "TVInputComposite1",
+        "TVInputComposite2",
+        "TVInputHDMI1",
+        "TVInputHDMI2",
+        "TVInputHDMI3",
+        "TVInputHDMI4",
+        "TVInputVGA1",
+        "TVMediaContext",
+        "TVNetwork",
+        "TVNumberEntry",
+        "TVPower",
+        "TVRadioService",
+        "TVSatellite",
+        "TVSatelliteBS",
+        "TVSatelliteCS",
+        "TVSatelliteToggle",
+        "TVTerrestrialAnalog",
+        "TVTerrestrialDigital",
+        "TVTimer",
+        "AVRInput",
+        "AVRPower",
+        "ColorF0Red",
+        "ColorF1Green",
+        "ColorF2Yellow",
+        "ColorF3Blue",
+        "ColorF4Grey",
+        "ColorF5Brown",
+        "ClosedCaptionToggle",
+        "Dimmer",
+        "DisplaySwap",
+        "DVR",
+        "Exit",
+        "FavoriteClear0",
+        "FavoriteClear1",
+        "FavoriteClear2",
+        "FavoriteClear3",
+        "FavoriteRecall0",
+        "FavoriteRecall1",
+        "FavoriteRecall2",
+        "FavoriteRecall3",
+        "FavoriteStore0",
+        "FavoriteStore1",
+        "FavoriteStore2",
+        "FavoriteStore3",
+        "Guide",
+        "GuideNextDay",
+        "GuidePreviousDay",
+        "Info",
+        "InstantReplay",
+        "Link",
+        "ListProgram",
+        "LiveContent",
+        "Lock",
+        "MediaApps",
+        "MediaAudioTrack",
+        "MediaLast",
+        "MediaSkipBackward",
+        "MediaSkipForward",
+        "MediaStepBackward",
+        "MediaStepForward",
+        "MediaTopMenu",
+        "NavigateIn",
+        "NavigateNext",
+        "NavigateOut",
+        "NavigatePrevious",
+        "NextFavoriteChannel",
+        "NextUserProfile",
+        "OnDemand",
+        "Pairing",
+        "PinPDown",
+        "PinPMove",
+        "PinPToggle",
+        "PinPUp",
+        "PlaySpeedDown",
+        "PlaySpeedReset",
+        "PlaySpeedUp",
+        "RandomToggle",
+        "RcLowBattery",
+        "RecordSpeedNext",
+        "RfBypass",
+        "ScanChannelsToggle",
+        "ScreenModeNext",
+        "Settings",
+        "SplitScreenToggle",
+        "STBInput",
+        "STBPower",
+        "Subtitle",
+        "Teletext",
+        "VideoModeNext",
+        "Wink",
+        "ZoomToggle",
+        "F1",
+        "F2",
+        "F3",
+        "F4",
+        "F5",
+        "F6",
+        "F7",
+        "F8",
+        "F9",
+        "F10",
+        "F11",
+        "F12",
+        "F13",
+        "F14",
+        "F15",
+        "F16",
+        "F17",
+        "F18",
+        "F19",
+        "F20",
+        "F21",
+        "F22",
+        "F23",
+        "F24",
+        "F25",
+        "F26",
+        "F27",
+        "F28",
+        "F29",
+        "F30",
+        "F31",
+        "F32",
+        "F33",
+        "F34",
+        "F35",
+    ];
+
+    /// (code, KeyCode 变体名, 别名)
+    const CODES: &[(&str, &str, &[&str])] = &[
+        ("Backquote", "Backquote", &[]),
+        ("Backslash", "Backslash", &[]),
+        ("BracketLeft", "BracketLeft", &[]),
+        ("BracketRight", "BracketRight", &[]),
+        ("Comma", "Comma", &[]),
+        ("Digit0", "Digit0", &[]),
+        ("Digit1", "Digit1", &[]),
+        ("Digit2", "Digit2", &[]),
+        ("Digit3", "Digit3", &[]),
+        ("Digit4", "Digit4", &[]),
+        ("Digit5", "Digit5", &[]),
+        ("Digit6", "Digit6", &[]),
+        ("Digit7", "Digit7", &[]),
+        ("Digit8", "Digit8", &[]),
+        ("Digit9", "Digit9", &[]),
+        ("Equal", "Equal", &[]),
+        ("IntlBackslash", "IntlBackslash", &[]),
+        ("IntlRo", "IntlRo", &[]),
+        ("IntlYen", "IntlYen", &[]),
+        ("KeyA", "KeyA", &[]),
+        ("KeyB", "KeyB", &[]),
+        ("KeyC", "KeyC", &[]),
+        ("KeyD", "KeyD", &[]),
+        ("KeyE", "KeyE", &[]),
+        ("KeyF", "KeyF", &[]),
+        ("KeyG", "KeyG", &[]),
+        ("KeyH", "KeyH", &[]),
+        ("KeyI", "KeyI", &[]),
+        ("KeyJ", "KeyJ", &[]),
+        ("KeyK", "KeyK", &[]),
+        ("KeyL", "KeyL", &[]),
+        ("KeyM", "KeyM", &[]),
+        ("KeyN", "KeyN", &[]),
+        ("KeyO", "KeyO", &[]),
+        ("KeyP", "KeyP", &[]),
+        ("KeyQ", "KeyQ", &[]),
+        ("KeyR", "KeyR", &[]),
+        ("KeyS", "KeyS", &[]),
+        ("KeyT", "KeyT", &[]),
+        ("KeyU", "KeyU", &[]),
+        ("KeyV", "KeyV", &[]),
+        ("KeyW", "KeyW", &[]),
+        ("KeyX", "KeyX", &[]),
+        ("KeyY", "KeyY", &[]),
+        ("KeyZ", "KeyZ", &[]),
+        ("Minus", "Minus", &[]),
+        ("Period", "Period", &[]),
+        ("Quote", "Quote", &[]),
+        ("Semicolon", "Semicolon", &[]),
+        ("Slash", "Slash", &[]),
+        ("AltLeft", "AltLeft", &[]),
+        ("AltRight", "AltRight", &[]),
+        ("Backspace", "Backspace", &[]),
+        ("CapsLock", "CapsLock", &[]),
+        ("ContextMenu", "ContextMenu", &[]),
+        ("ControlLeft", "ControlLeft", &[]),
+        ("ControlRight", "ControlRight", &[]),
+        ("Enter", "Enter", &[]),
+        ("MetaLeft", "SuperLeft", &["OSLeft"]),
+        ("MetaRight", "SuperRight", &["OSRight"]),
+        ("ShiftLeft", "ShiftLeft", &[]),
+        ("ShiftRight", "ShiftRight", &[]),
+        ("Space", "Space", &[]),
+        ("Tab", "Tab", &[]),
+        ("Convert", "Convert", &[]),
+        ("KanaMode", "KanaMode", &[]),
+        ("Lang1", "Lang1", &[]),
+        ("Lang2", "Lang2", &[]),
+        ("Lang3", "Lang3", &[]),
+        ("Lang4", "Lang4", &[]),
+        ("Lang5", "Lang5", &[]),
+        ("NonConvert", "NonConvert", &[]),
+        ("Delete", "Delete", &[]),
+        ("End", "End", &[]),
+        ("Help", "Help", &[]),
+        ("Home", "Home", &[]),
+        ("Insert", "Insert", &[]),
+        ("PageDown", "PageDown", &[]),
+        ("PageUp", "PageUp", &[]),
+        ("ArrowDown", "ArrowDown", &[]),
+        ("ArrowLeft", "ArrowLeft", &[]),
+        ("ArrowRight", "ArrowRight", &[]),
+        ("ArrowUp", "ArrowUp", &[]),
+        ("NumLock", "NumLock", &[]),
+        ("Numpad0", "Numpad0", &[]),
+        ("Numpad1", "Numpad1", &[]),
+        ("Numpad2", "Numpad2", &[]),
+        ("Numpad3", "Numpad3", &[]),
+        ("Numpad4", "Numpad4", &[]),
+        ("Numpad5", "Numpad5", &[]),
+        ("Numpad6", "Numpad6", &[]),
+        ("Numpad7", "Numpad7", &[]),
+        ("Numpad8", "Numpad8", &[]),
+        ("Numpad9", "Numpad9", &[]),
+        ("NumpadAdd", "NumpadAdd", &[]),
+        ("NumpadBackspace", "NumpadBackspace", &[]),
+        ("NumpadClear", "NumpadClear", &[]),
+        ("NumpadClearEntry", "NumpadClearEntry", &[]),
+        ("NumpadComma", "NumpadComma", &[]),
+        ("NumpadDecimal", "NumpadDecimal", &[]),
+        ("NumpadDivide", "NumpadDivide", &[]),
+        ("NumpadEnter", "NumpadEnter", &[]),
+        ("NumpadEqual", "NumpadEqual", &[]),
+        ("NumpadHash", "NumpadHash", &[]),
+        ("NumpadMemoryAdd", "NumpadMemoryAdd", &[]),
+        ("NumpadMemoryClear", "NumpadMemoryClear", &[]),
+        ("NumpadMemoryRecall", "NumpadMemoryRecall", &[]),
+        ("NumpadMemoryStore", "NumpadMemoryStore", &[]),
+        ("NumpadMemorySubtract", "NumpadMemorySubtract", &[]),
+        ("NumpadMultiply", "NumpadMultiply", &[]),
+        ("NumpadParenLeft", "NumpadParenLeft", &[]),
+        ("NumpadParenRight", "NumpadParenRight", &[]),
+        ("NumpadStar", "NumpadStar", &[]),
+        ("NumpadSubtract", "NumpadSubtract", &[]),
+        ("Escape", "Escape", &[]),
+        ("Fn", "Fn", &[]),
+        ("FnLock", "FnLock", &[]),
+        ("PrintScreen", "PrintScreen", &[]),
+        ("ScrollLock", "ScrollLock", &[]),
+        ("Pause", "Pause", &[]),
+        ("BrowserBack", "BrowserBack", &[]),
+        ("BrowserFavorites", "BrowserFavorites", &[]),
+        ("BrowserForward", "BrowserForward", &[]),
+        ("BrowserHome", "BrowserHome", &[]),
+        ("BrowserRefresh", "BrowserRefresh", &[]),
+        ("BrowserSearch", "BrowserSearch", &[]),
+        ("BrowserStop", "BrowserStop", &[]),
+        ("Eject", "Eject", &[]),
+        ("LaunchApp1", "LaunchApp1", &[]),
+        ("LaunchApp2", "LaunchApp2", &[]),
+        ("LaunchMail", "LaunchMail", &[]),
+        ("MediaPlayPause", "MediaPlayPause", &[]),
+        ("MediaSelect", "MediaSelect", &["LaunchMediaPlayer"]),
+        ("MediaStop", "MediaStop", &[]),
+        ("MediaTrackNext", "MediaTrackNext", &[]),
+        ("MediaTrackPrevious", "MediaTrackPrevious", &[]),
+        ("Power", "Power", &[]),
+        ("Sleep", "Sleep", &[]),
+        ("AudioVolumeDown", "AudioVolumeDown", &["VolumeDown"]),
+        ("AudioVolumeMute", "AudioVolumeMute", &["VolumeMute"]),
+        ("AudioVolumeUp", "AudioVolumeUp", &["VolumeUp"]),
+        ("WakeUp", "WakeUp", &[]),
+        ("Hyper", "Hyper", &[]),
+        ("Super", "Meta", &[]),
+        ("Turbo", "Turbo", &[]),
+        ("Abort", "Abort", &[]),
+        ("Resume", "Resume", &[]),
+        ("Suspend", "Suspend", &[]),
+        ("Again", "Again", &[]),
+        ("Copy", "Copy", &[]),
+        ("Cut", "Cut", &[]),
+        ("Find", "Find", &[]),
+        ("Open", "Open", &[]),
+        ("Paste", "Paste", &[]),
+        ("Props", "Props", &[]),
+        ("Select", "Select", &[]),
+        ("Undo", "Undo", &[]),
+        ("Hiragana", "Hiragana", &[]),
+        ("Katakana", "Katakana", &[]),
+        ("F1", "F1", &[]),
+        ("F2", "F2", &[]),
+        ("F3", "F3", &[]),
+        ("F4", "F4", &[]),
+        ("F5", "F5", &[]),
+        ("F6", "F6", &[]),
+        ("F7", "F7", &[]),
+        ("F8", "F8", &[]),
+        ("F9", "F9", &[]),
+        ("F10", "F10", &[]),
+        ("F11", "F11", &[]),
+        ("F12", "F12", &[]),
+        ("F13", "F13", &[]),
+        ("F14", "F14", &[]),
+        ("F15", "F15", &[]),
+        ("F16", "F16", &[]),
+        ("F17", "F17", &[]),
+        ("F18", "F18", &[]),
+        ("F19", "F19", &[]),
+        ("F20", "F20", &[]),
+        ("F21", "F21", &[]),
+        ("F22", "F22", &[]),
+        ("F23", "F23", &[]),
+        ("F24", "F24", &[]),
+        ("F25", "F25", &[]),
+        ("F26", "F26", &[]),
+        ("F27", "F27", &[]),
+        ("F28", "F28", &[]),
+        ("F29", "F29", &[]),
+        ("F30", "F30", &[]),
+        ("F31", "F31", &[]),
+        ("F32", "F32", &[]),
+        ("F33", "F33", &[]),
+        ("F34", "F34", &[]),
+        ("F35", "F35", &[]),
+    ];
+
+    #[test]
+    fn keys_round_trip() {
+        for &key in KEYS {
+            match key.as_key() {
+                Key::Dead(None) => assert_eq!(key, "Dead"),
+                Key::Unidentified(NativeKey::Web(name)) => assert_eq!(key, name),
+                converted => assert_eq!(format!("{converted:?}"), key),
+            }
+            assert_eq!(key.as_key().to_w3c_key(), key);
+        }
+    }
+
+    #[test]
+    fn codes_round_trip() {
+        for &(code, variant, alternatives) in CODES {
+            let converted = code.as_key_code();
+            assert_eq!(format!("{converted:?}"), variant, "{code}");
+            assert_eq!(converted.to_w3c_code(), code);
+            for alternative in alternatives {
+                assert_eq!(alternative.as_key_code(), converted, "{alternative}");
+                assert_eq!(alternative.as_key_code().to_w3c_code(), code);
+            }
+        }
+    }
+
+    #[test]
+    fn characters() {
+        for text in ["a", "A", "1", " ", "é", "e\u{301}", "€", "ß", "あ", "👍🏽", "👨‍👩‍👧"] {
+            assert_eq!(text.as_key(), Key::Character(SmolStr::new(text)));
+            assert_eq!(text.as_key().to_w3c_key(), text);
+        }
+        assert_eq!(Key::Space.to_w3c_key(), " ");
+    }
+
+    #[test]
+    fn unknown_names() {
+        for name in ["", "KeyA", "NotAKey", "ab"] {
+            assert_eq!(
+                name.as_key(),
+                Key::Unidentified(NativeKey::Web(SmolStr::new(name)))
+            );
+            assert_eq!(name.as_key().to_w3c_key(), name);
+        }
+        assert_eq!(Key::Unidentified(NativeKey::Unidentified).to_w3c_key(), "Unidentified");
+        assert_eq!(
+            "NotACode".as_key_code(),
+            KeyCode::Unidentified(NativeKeyCode::Unidentified)
+        );
+        assert_eq!(
+            KeyCode::Unidentified(NativeKeyCode::Xkb(0)).to_w3c_code(),
+            "Unidentified"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! 输入延迟统计
+//!
+//! 宿主消息携带事件产生的时间，worker 记录两段延迟：
+//! 排队（事件产生到帧开始时被取出，包含 `block_from_worker` 等帧前的阻塞）
+//! 与端到端（事件产生到该帧 update 结束，此时画面已提交）。
+//! 每隔 [`InputLatencySettings::report_interval`] 把分位数以 [`WorkerToHost::InputStats`] 发给宿主
+
+use crate::{
+    WorkerApp,
+    bridge::Host,
+    coalesce::InputCoalescingStats,
+    protocol::{HostToWorker, Percentiles, WorkerToHost},
+};
+use bevy::prelude::*;
+
+#[derive(Resource, Debug, Clone)]
+pub struct InputLatencySettings {
+    /// 上报间隔（毫秒），为 0 时不上报
+    pub report_interval: f64,
+}
+
+impl Default for InputLatencySettings {
+    fn default() -> Self {
+        Self {
+            report_interval: 1000.,
+        }
+    }
+}
+
+/// 当前统计周期内的样本（毫秒）
+#[derive(Resource, Debug, Default, Clone)]
+pub struct InputLatency {
+    pub queue_delay: Vec<f64>,
+    pub latency: Vec<f64>,
+    /// 当前周期的开始时间
+    since: Option<f64>,
+    /// 上次上报时 [`InputCoalescingStats`] 的累计 (received, merged)
+    reported: (u64, u64),
+}
+
+impl WorkerApp {
+    /// 在 update 之后调用：`received` 为取出这批消息的时间，`stamps` 为其中各输入的时间戳
+    pub(crate) fn record_input_latency(&mut self, received: f64, stamps: &[f64]) {
+        let host = self.world().resource::<Host>().clone();
+        let coalescing = self.world().resource::<InputCoalescingStats>();
+        let coalescing = (coalescing.received, coalescing.merged);
+        let interval = self
+            .world()
+            .resource::<InputLatencySettings>()
+            .report_interval;
+        let presented = host.now();
+
+        let mut stats = self.world_mut().resource_mut::<InputLatency>();
+        stats
+            .queue_delay
+            .extend(stamps.iter().map(|t| received - t));
+        stats.latency.extend(stamps.iter().map(|t| presented - t));
+        let since = *stats.since.get_or_insert(received);
+        if interval <= 0. || presented - since < interval {
+            return;
+        }
+
+        stats.since = Some(presented);
+        let queue_delay = std::mem::take(&mut stats.queue_delay);
+        let latency = std::mem::take(&mut stats.latency);
+        let reported = std::mem::replace(&mut stats.reported, coalescing);
+        if !latency.is_empty() {
+            host.post(WorkerToHost::InputStats {
+                events: latency.len(),
+                queue_delay: percentiles(queue_delay),
+                latency: percentiles(latency),
+                received: coalescing.0 - reported.0,
+                merged: coalescing.1 - reported.1,
+            });
+        }
+    }
+}
+
+/// 输入事件的宿主时间戳，没有携带时（旧版宿主）为 `None`
+pub(crate) fn time_stamp(msg: &HostToWorker) -> Option<f64> {
+    let t = match msg {
+        HostToWorker::MouseUp(ev) | HostToWorker::MouseDown(ev) | HostToWorker::MouseMove(ev) => {
+            ev.time_stamp
+        }
+        HostToWorker::KeyUp(ev) | HostToWorker::KeyDown(ev) => ev.time_stamp,
+        HostToWorker::Wheel(ev) => ev.time_stamp,
+        HostToWorker::CompositionStart(ev)
+        | HostToWorker::CompositionUpdate(ev)
+        | HostToWorker::CompositionEnd(ev) => ev.time_stamp,
+        HostToWorker::PointerDown(ev)
+        | HostToWorker::PointerMove(ev)
+        | HostToWorker::PointerUp(ev)
+        | HostToWorker::PointerCancel(ev)
+        | HostToWorker::PointerEnter(ev)
+        | HostToWorker::PointerLeave(ev) => ev.time_stamp,
+        _ => return None,
+    };
+    (t > 0.).then_some(t)
+}
+
+/// 最近秩法
+fn percentiles(mut samples: Vec<f64>) -> Percentiles {
+    samples.sort_by(f64::total_cmp);
+    let rank = |p: f64| samples[((p * samples.len() as f64).ceil() as usize).max(1) - 1];
+    Percentiles {
+        p50: rank(0.5),
+        p90: rank(0.9),
+        p99: rank(0.99),
+        max: samples[samples.len() - 1],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{PointerEvent, PointerType},
+        test_harness::{Harness, pointer},
+    };
+
+    fn moved(time_stamp: f64) -> HostToWorker {
+        HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            time_stamp,
+            ..pointer(PointerType::Mouse, 1, 0., 0.)
+        })
+    }
+
+    fn input_stats(h: &Harness) -> Vec<WorkerToHost> {
+        h.bridge
+            .take_posted()
+            .into_iter()
+            .filter(|msg| matches!(msg, WorkerToHost::InputStats { .. }))
+            .collect()
+    }
+
+    #[test]
+    fn percentiles_nearest_rank() {
+        let samples = (1..=100).map(f64::from).rev().collect();
+        assert_eq!(
+            percentiles(samples),
+            Percentiles {
+                p50: 50.,
+                p90: 90.,
+                p99: 99.,
+                max: 100.
+            }
+        );
+        assert_eq!(
+            percentiles(vec![3.]),
+            Percentiles {
+                p50: 3.,
+                p90: 3.,
+                p99: 3.,
+                max: 3.
+            }
+        );
+    }
+
+    #[test]
+    fn records_and_reports_latency() {
+        let mut h = Harness::new(1.0);
+
+        h.bridge.set_now(1000.);
+        h.bridge.send(moved(990.));
+        h.bridge.send(moved(996.));
+        h.send(HostToWorker::Focus(true));
+        let stats = h.world().resource::<InputLatency>();
+        assert_eq!(stats.queue_delay, vec![10., 4.]);
+        assert_eq!(stats.latency, vec![10., 4.]);
+        assert!(input_stats(&h).is_empty());
+
+        // 不满一个周期不上报
+        h.bridge.set_now(1500.);
+        h.send(moved(1480.));
+        assert!(input_stats(&h).is_empty());
+
+        h.bridge.set_now(2000.);
+        h.send(moved(1990.));
+        assert_eq!(
+            input_stats(&h),
+            vec![WorkerToHost::InputStats {
+                events: 4,
+                queue_delay: Percentiles {
+                    p50: 10.,
+                    p90: 20.,
+                    p99: 20.,
+                    max: 20.
+                },
+                latency: Percentiles {
+                    p50: 10.,
+                    p90: 20.,
+                    p99: 20.,
+                    max: 20.
+                },
+                received: 5,
+                merged: 1,
+            }]
+        );
+        let stats = h.world().resource::<InputLatency>();
+        assert!(stats.latency.is_empty());
+
+        // 没有时间戳的消息不计入
+        h.send(moved(0.));
+        assert!(h.world().resource::<InputLatency>().latency.is_empty());
+    }
+}
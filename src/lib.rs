@@ -1,19 +1,47 @@
-use bevy::{app::PluginsState, ecs::system::SystemState, prelude::*, window::WindowCloseRequested};
+use bevy::{
+    app::PluginsState,
+    ecs::system::SystemState,
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+    window::{
+        CursorGrabMode, CursorOptions, WindowBackendScaleFactorChanged, WindowCloseRequested,
+        WindowEvent, WindowFocused, WindowResized, WindowScaleFactorChanged,
+    },
+};
 use bevy_input::{
     ButtonState,
-    keyboard::{Key, KeyboardInput},
-    mouse::{MouseButtonInput, MouseScrollUnit, MouseWheel},
+    keyboard::{Key, KeyboardFocusLost, KeyboardInput},
+    mouse::{MouseButtonInput, MouseMotion},
 };
 use smol_str::SmolStr;
 use std::ops::{Deref, DerefMut};
 
 mod bevy_app;
+pub mod bridge;
 mod canvas_plugin;
-mod keyboard;
+pub mod click;
+pub mod clipboard;
+pub mod coalesce;
+pub mod cursor;
+mod gamepad;
+pub mod gesture;
+pub mod ime;
+pub mod keyboard;
+pub mod latency;
+mod picking;
+pub mod pointer;
+pub mod protocol;
 mod ray_pick;
+#[cfg(test)]
+mod test_harness;
 mod web_ffi;
+pub mod wheel;
 
+use bevy_input::touch::TouchPhase;
+use bridge::{Host, HostBridge};
+use ime::CompositionPhase;
 use keyboard::{AsKey, AsKeyCode};
+use protocol::{HostToWorker, PROTOCOL_VERSION, WorkerToHost};
 
 pub(crate) use canvas_plugin::{OffscreenCanvas, OffscreenCanvasPlugin};
 
@@ -21,7 +49,13 @@ pub struct WorkerApp {
     pub app: App,
     /// 手动包装事件需要
     pub window: Entity,
-    pub scale_factor: f32,
+    /// 已发出按下、尚未松开的键及其逻辑键
+    ///
+    /// 同一帧内的消息在 update 之前处理，此时 `ButtonInput` 还未反映它们，
+    /// 因此在这里自行记录
+    held_keys: HashMap<KeyCode, Key>,
+    held_buttons: HashSet<MouseButton>,
+    ime: ime::ImeState,
 }
 
 impl Deref for WorkerApp {
@@ -39,18 +73,25 @@ impl DerefMut for WorkerApp {
 }
 
 impl WorkerApp {
-    pub fn new(app: App, scale_factor: f32) -> Self {
+    pub fn new(mut app: App, host: impl HostBridge) -> Self {
+        app.insert_resource(Host::new(host))
+            .init_resource::<pointer::Pen>()
+            .init_resource::<gamepad::WebGamepads>()
+            .init_resource::<coalesce::InputCoalescing>()
+            .init_resource::<coalesce::InputCoalescingStats>()
+            .init_resource::<latency::InputLatencySettings>()
+            .init_resource::<latency::InputLatency>()
+            .init_resource::<wheel::WheelSettings>()
+            .add_message::<wheel::MouseWheelZ>();
         Self {
             app,
             window: Entity::PLACEHOLDER,
-            scale_factor,
+            held_keys: HashMap::default(),
+            held_buttons: HashSet::default(),
+            ime: default(),
         }
     }
 
-    pub fn to_physical_size(&self, x: f32, y: f32) -> Vec2 {
-        Vec2::new(x * self.scale_factor, y * self.scale_factor)
-    }
-
     pub fn try_finish(&mut self) -> bool {
         if self.plugins_state() != PluginsState::Ready {
             return false;
@@ -68,71 +109,270 @@ impl WorkerApp {
         true
     }
 
+    /// 执行一帧：处理宿主发来的消息并 update，随后请求下一帧
+    pub fn frame(&mut self) {
+        let host = self.world().resource::<Host>().clone();
+        if self.window == Entity::PLACEHOLDER {
+            if self.try_finish() {
+                host.post(WorkerToHost::Ready {
+                    version: PROTOCOL_VERSION,
+                });
+            }
+            host.schedule_frame();
+            return;
+        }
+
+        let batch: Vec<_> = std::iter::from_fn(|| host.recv()).collect();
+        let received = host.now();
+        let stamps: Vec<_> = batch.iter().filter_map(latency::time_stamp).collect();
+        for msg in self.coalesce(batch) {
+            self.on_host_message(msg);
+        }
+
+        self.update();
+        if self.should_exit().is_some() {
+            self.close_window();
+            return;
+        }
+        self.record_input_latency(received, &stamps);
+
+        host.schedule_frame();
+    }
+
+    fn on_host_message(&mut self, msg: HostToWorker) {
+        info!("[worker] <- {:?}", &msg);
+        let modifiers = modifiers(&msg);
+        // 事件自身的键以事件为准，不参与修饰键同步
+        let mut own_key = None;
+        match msg {
+            HostToWorker::MouseUp(ev) => self.on_mouse_up(ev.button),
+            HostToWorker::MouseDown(ev) => self.on_mouse_down(ev.button),
+            HostToWorker::MouseMove(ev) => self.on_mouse_move(
+                Vec2::new(ev.offset_x, ev.offset_y),
+                Vec2::new(ev.movement_x, ev.movement_y),
+            ),
+            HostToWorker::KeyUp(ev) => own_key = Some(self.on_key_up(ev)),
+            HostToWorker::KeyDown(ev) => own_key = Some(self.on_key_down(ev)),
+            HostToWorker::Wheel(ev) => self.on_wheel(ev),
+            HostToWorker::CompositionStart(ev) => self.on_composition(CompositionPhase::Start, ev),
+            HostToWorker::CompositionUpdate(ev) => {
+                self.on_composition(CompositionPhase::Update, ev)
+            }
+            HostToWorker::CompositionEnd(ev) => self.on_composition(CompositionPhase::End, ev),
+            HostToWorker::PointerDown(ev) => self.on_pointer(TouchPhase::Started, ev),
+            HostToWorker::PointerMove(ev) => self.on_pointer(TouchPhase::Moved, ev),
+            HostToWorker::PointerUp(ev) => self.on_pointer(TouchPhase::Ended, ev),
+            HostToWorker::PointerCancel(ev) => self.on_pointer(TouchPhase::Canceled, ev),
+            HostToWorker::PointerEnter(ev) => self.on_pointer_crossing(true, ev),
+            HostToWorker::PointerLeave(ev) => self.on_pointer_crossing(false, ev),
+            HostToWorker::Resize(ev) => self.on_resize(ev),
+            HostToWorker::Focus(focused) => self.on_focus(focused),
+            HostToWorker::PointerLock(locked) => self.on_pointer_lock(locked),
+            HostToWorker::Clipboard(ev) => self.on_clipboard(ev),
+            HostToWorker::Gamepads(pads) => self.on_gamepads(pads),
+            // 只影响 js 侧的阻塞时长，由 WorkerBridge 直接处理
+            HostToWorker::BlockRender(_) => {}
+        }
+        if let Some(modifiers) = modifiers {
+            self.sync_modifiers(modifiers, own_key);
+        }
+    }
+
     fn on_mouse_up(&mut self, button: i16) {
-        let window = self.window;
-        self.world_mut().write_message(MouseButtonInput {
-            button: mouse_button(button),
-            state: ButtonState::Released,
-            window,
-        });
+        self.write_mouse_button(mouse_button(button), ButtonState::Released);
+    }
+
+    fn on_mouse_down(&mut self, button: i16) {
+        self.write_mouse_button(mouse_button(button), ButtonState::Pressed);
     }
 
-    fn on_mouse_down(&mut self, button: i16, x: f32, y: f32) {
+    fn write_mouse_button(&mut self, button: MouseButton, state: ButtonState) {
+        match state {
+            ButtonState::Pressed => self.held_buttons.insert(button),
+            ButtonState::Released => self.held_buttons.remove(&button),
+        };
         let window = self.window;
-        self.world_mut().write_message(MouseButtonInput {
-            button: mouse_button(button),
-            state: ButtonState::Pressed,
-            window,
-        });
+        write_window_event(
+            self.world_mut(),
+            MouseButtonInput {
+                button,
+                state,
+                window,
+            },
+        );
     }
 
-    fn on_mouse_move(&mut self, x: f32, y: f32) {
-        // 将逻辑像转换成物理像素
+    /// offsetX/Y 是相对画布的 CSS 像素，即 bevy 的逻辑坐标。
+    /// 与 bevy_winit 一致，物理坐标记录到 `Window` 上，`CursorMoved.delta` 取自上一次的位置；
+    /// movementX/Y 换算为物理像素后作为 `MouseMotion`。
+    /// 指针锁定时 offsetX/Y 停在锁定处，只产生 `MouseMotion`
+    fn on_mouse_move(&mut self, position: Vec2, movement: Vec2) {
         let window = self.window;
-        let position = self.to_physical_size(x, y);
-        self.world_mut().write_message(CursorMoved {
-            position,
-            delta: None,
-            window,
-        });
+        let world = self.world_mut();
+        let locked = world
+            .get::<CursorOptions>(window)
+            .is_some_and(|cursor| cursor.grab_mode == CursorGrabMode::Locked);
+        let Some(mut w) = world.get_mut::<Window>(window) else {
+            return;
+        };
+        let scale_factor = w.scale_factor();
+
+        if !locked {
+            let delta = w
+                .physical_cursor_position()
+                .map(|last| position - last / scale_factor);
+            w.set_physical_cursor_position(Some(position.as_dvec2() * scale_factor as f64));
+            write_window_event(
+                world,
+                CursorMoved {
+                    position,
+                    delta,
+                    window,
+                },
+            );
+        }
+        if movement != Vec2::ZERO {
+            write_window_event(
+                world,
+                MouseMotion {
+                    delta: movement * scale_factor,
+                },
+            );
+        }
+    }
+
+    fn on_key_up(&mut self, ev: protocol::KeyboardEvent) -> KeyCode {
+        let input = keyboard_input(ev, ButtonState::Released, self.window);
+        if self.ime.swallow_release(input.key_code) {
+            return input.key_code;
+        }
+        self.write_keyboard_input(input)
     }
 
-    fn on_key_up(&mut self, code: String, repeat: bool) {
+    fn on_key_down(&mut self, ev: protocol::KeyboardEvent) -> KeyCode {
+        // 输入法处理中的按键 `key` 为 "Process"
+        let composing = ev.is_composing || ev.key == "Process";
+        let input = keyboard_input(ev, ButtonState::Pressed, self.window);
+        if self.ime.swallow_press(input.key_code, composing) {
+            return input.key_code;
+        }
+        self.write_keyboard_input(input)
+    }
+
+    fn write_keyboard_input(&mut self, input: KeyboardInput) -> KeyCode {
+        let key_code = input.key_code;
+        match input.state {
+            ButtonState::Pressed => {
+                self.held_keys.insert(key_code, input.logical_key.clone());
+            }
+            ButtonState::Released => {
+                self.held_keys.remove(&key_code);
+            }
+        }
+        write_window_event(self.world_mut(), input);
+        key_code
+    }
+
+    /// 合成按下/松开事件，使修饰键与事件携带的状态一致
+    ///
+    /// 修饰键在容器外按下或松开时，容器收不到对应的 keydown / keyup
+    fn sync_modifiers(&mut self, modifiers: [bool; 4], own_key: Option<KeyCode>) {
+        for (pressed, (left, right, logical_key)) in modifiers.into_iter().zip(MODIFIERS) {
+            if own_key.is_some_and(|key| key == left || key == right) {
+                continue;
+            }
+            let held: Vec<_> = [left, right]
+                .into_iter()
+                .filter(|key| self.held_keys.contains_key(key))
+                .collect();
+            if pressed && held.is_empty() {
+                self.write_synthetic_key(left, logical_key, ButtonState::Pressed);
+            } else if !pressed {
+                for key_code in held {
+                    self.write_synthetic_key(key_code, logical_key.clone(), ButtonState::Released);
+                }
+            }
+        }
+    }
+
+    fn write_synthetic_key(&mut self, key_code: KeyCode, logical_key: Key, state: ButtonState) {
         let window = self.window;
-        let key = code.as_str().as_key();
-        let text = key_text(&key);
-        self.world_mut().write_message(KeyboardInput {
-            key_code: code.as_str().as_key_code(),
-            logical_key: key,
-            state: ButtonState::Released,
-            text: text,
-            repeat,
+        self.write_keyboard_input(KeyboardInput {
+            key_code,
+            logical_key,
+            state,
+            text: None,
+            repeat: false,
             window,
         });
     }
 
-    fn on_key_down(&mut self, code: String, repeat: bool) {
+    /// 与 bevy_winit 一致：先更新 `Window`，再依次发出缩放与尺寸改变的消息。
+    /// 画布与 surface 随 `Window` 的改变在 `changed_window` 和渲染提取阶段更新
+    fn on_resize(&mut self, ev: protocol::ResizeEvent) {
         let window = self.window;
-        let key = code.as_str().as_key();
-        let text = key_text(&key);
-        self.world_mut().write_message(KeyboardInput {
-            key_code: code.as_str().as_key_code(),
-            logical_key: key,
-            state: ButtonState::Pressed,
-            text: text,
-            repeat,
-            window,
-        });
+        let scale_factor = ev.device_pixel_ratio;
+        let physical_width = (ev.width * scale_factor).round() as u32;
+        let physical_height = (ev.height * scale_factor).round() as u32;
+        let Some(mut w) = self.world_mut().get_mut::<Window>(window) else {
+            return;
+        };
+
+        let scale_changed = w.resolution.base_scale_factor() != scale_factor;
+        let size_changed = w.physical_size() != UVec2::new(physical_width, physical_height);
+        if !scale_changed && !size_changed {
+            return;
+        }
+        w.resolution.set_scale_factor(scale_factor);
+        w.resolution
+            .set_physical_resolution(physical_width, physical_height);
+        let (width, height) = (w.width(), w.height());
+
+        if scale_changed {
+            let scale_factor = scale_factor as f64;
+            write_window_event(
+                self.world_mut(),
+                WindowBackendScaleFactorChanged {
+                    window,
+                    scale_factor,
+                },
+            );
+            write_window_event(
+                self.world_mut(),
+                WindowScaleFactorChanged {
+                    window,
+                    scale_factor,
+                },
+            );
+        }
+        write_window_event(
+            self.world_mut(),
+            WindowResized {
+                window,
+                width,
+                height,
+            },
+        );
     }
 
-    fn on_wheel(&mut self, delta_x: f32, delta_y: f32, delta_mode: u8) {
+    /// 失去焦点后收不到后续的 keyup / mouseup，松开所有按住的键和鼠标按钮
+    fn on_focus(&mut self, focused: bool) {
         let window = self.window;
-        self.world_mut().write_message(MouseWheel {
-            x: delta_x,
-            y: delta_y,
-            unit: mouse_scroll_unit(delta_mode),
-            window,
-        });
+        if !focused {
+            for (key_code, logical_key) in std::mem::take(&mut self.held_keys) {
+                self.write_synthetic_key(key_code, logical_key, ButtonState::Released);
+            }
+            for button in std::mem::take(&mut self.held_buttons) {
+                self.write_mouse_button(button, ButtonState::Released);
+            }
+            self.cancel_composition();
+            write_window_event(self.world_mut(), KeyboardFocusLost);
+        }
+
+        if let Some(mut w) = self.world_mut().get_mut::<Window>(window) {
+            w.focused = focused;
+        }
+        write_window_event(self.world_mut(), WindowFocused { window, focused });
     }
 
     fn close_window(&mut self) {
@@ -140,14 +380,52 @@ impl WorkerApp {
             SystemState::from_world(self.world_mut());
         let windows = state.get_mut(self.world_mut());
         let (entity, _window) = windows.iter().last().unwrap();
-        self.world_mut()
-            .write_message(WindowCloseRequested { window: entity });
+        write_window_event(self.world_mut(), WindowCloseRequested { window: entity });
         state.apply(self.world_mut());
 
         self.update();
     }
 }
 
+/// 与 bevy_winit 一致，窗口与输入消息同时作为 `WindowEvent` 发出，
+/// bevy_picking 的指针输入等只读取后者
+pub(crate) fn write_window_event<M: Message + Clone + Into<WindowEvent>>(
+    world: &mut World,
+    msg: M,
+) {
+    world.write_message::<WindowEvent>(msg.clone().into());
+    world.write_message(msg);
+}
+
+/// 与 [`modifiers`] 的顺序一致：(左侧键, 右侧键, 逻辑键)
+const MODIFIERS: [(KeyCode, KeyCode, Key); 4] = [
+    (KeyCode::AltLeft, KeyCode::AltRight, Key::Alt),
+    (KeyCode::ControlLeft, KeyCode::ControlRight, Key::Control),
+    (KeyCode::SuperLeft, KeyCode::SuperRight, Key::Meta),
+    (KeyCode::ShiftLeft, KeyCode::ShiftRight, Key::Shift),
+];
+
+/// 事件携带的修饰键状态：[alt, ctrl, meta, shift]
+fn modifiers(msg: &HostToWorker) -> Option<[bool; 4]> {
+    match msg {
+        HostToWorker::MouseUp(ev) | HostToWorker::MouseDown(ev) | HostToWorker::MouseMove(ev) => {
+            Some([ev.alt_key, ev.ctrl_key, ev.meta_key, ev.shift_key])
+        }
+        HostToWorker::KeyUp(ev) | HostToWorker::KeyDown(ev) => {
+            Some([ev.alt_key, ev.ctrl_key, ev.meta_key, ev.shift_key])
+        }
+        HostToWorker::PointerDown(ev)
+        | HostToWorker::PointerMove(ev)
+        | HostToWorker::PointerUp(ev)
+        | HostToWorker::PointerCancel(ev)
+        | HostToWorker::PointerEnter(ev)
+        | HostToWorker::PointerLeave(ev) => {
+            Some([ev.alt_key, ev.ctrl_key, ev.meta_key, ev.shift_key])
+        }
+        _ => None,
+    }
+}
+
 fn mouse_button(button: i16) -> MouseButton {
     match button {
         0 => MouseButton::Left,
@@ -159,14 +437,6 @@ fn mouse_button(button: i16) -> MouseButton {
     }
 }
 
-fn mouse_scroll_unit(delta_mode: u8) -> MouseScrollUnit {
-    if delta_mode == 1 {
-        MouseScrollUnit::Line
-    } else {
-        MouseScrollUnit::Pixel
-    }
-}
-
 // https://github.com/rust-windowing/winit/blob/da6220060e7626c11332354cc26cd47e2937c200/winit-web/src/web_sys/event.rs#L265
 pub fn key_text(key: &Key) -> Option<SmolStr> {
     match key {
@@ -175,5 +445,515 @@ pub fn key_text(key: &Key) -> Option<SmolStr> {
         Key::Enter => Some(SmolStr::new("\r")),
         _ => None,
     }
-    .map(SmolStr::new)
+}
+
+/// 与 bevy_winit 在 web 上的转换保持一致：`key` -> 逻辑键，`code` -> 物理键
+fn keyboard_input(
+    ev: protocol::KeyboardEvent,
+    state: ButtonState,
+    window: Entity,
+) -> KeyboardInput {
+    let logical_key = ev.key.as_str().as_key();
+    KeyboardInput {
+        key_code: key_code(&ev.code, &logical_key, ev.location),
+        text: key_text(&logical_key),
+        logical_key,
+        state,
+        repeat: ev.repeat,
+        window,
+    }
+}
+
+/// 部分虚拟键盘的 `code` 为空，此时借助 `location` 从逻辑键推断物理键
+///
+/// https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/location
+fn key_code(code: &str, key: &Key, location: u32) -> KeyCode {
+    const LEFT: u32 = 1;
+    const RIGHT: u32 = 2;
+    const NUMPAD: u32 = 3;
+
+    let key_code = code.as_key_code();
+    if !matches!(key_code, KeyCode::Unidentified(_)) {
+        return key_code;
+    }
+
+    match (key, location) {
+        (Key::Shift, LEFT) => KeyCode::ShiftLeft,
+        (Key::Shift, RIGHT) => KeyCode::ShiftRight,
+        (Key::Control, LEFT) => KeyCode::ControlLeft,
+        (Key::Control, RIGHT) => KeyCode::ControlRight,
+        (Key::Alt, LEFT) => KeyCode::AltLeft,
+        (Key::Alt, RIGHT) => KeyCode::AltRight,
+        (Key::Meta | Key::Super, LEFT) => KeyCode::SuperLeft,
+        (Key::Meta | Key::Super, RIGHT) => KeyCode::SuperRight,
+        (Key::Enter, NUMPAD) => KeyCode::NumpadEnter,
+        (Key::Character(c), NUMPAD) => match c.as_str() {
+            "0" => KeyCode::Numpad0,
+            "1" => KeyCode::Numpad1,
+            "2" => KeyCode::Numpad2,
+            "3" => KeyCode::Numpad3,
+            "4" => KeyCode::Numpad4,
+            "5" => KeyCode::Numpad5,
+            "6" => KeyCode::Numpad6,
+            "7" => KeyCode::Numpad7,
+            "8" => KeyCode::Numpad8,
+            "9" => KeyCode::Numpad9,
+            "+" => KeyCode::NumpadAdd,
+            "-" => KeyCode::NumpadSubtract,
+            "*" => KeyCode::NumpadMultiply,
+            "/" => KeyCode::NumpadDivide,
+            "." | "," => KeyCode::NumpadDecimal,
+            "=" => KeyCode::NumpadEqual,
+            _ => key_code,
+        },
+        _ => key_code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{MouseEvent, ResizeEvent},
+        test_harness::{Harness, key, mouse},
+    };
+    use bevy::input::{keyboard::NativeKeyCode, mouse::AccumulatedMouseMotion};
+
+    /// 不一致时执行 `make keyboard` 重新生成。
+    /// 只能发现手改 src/keyboard.rs 或生成器的改动，spec/ 下的表格是否与规范一致须另行核对
+    #[test]
+    fn keyboard_matches_generator() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/keyboard.rs"));
+        assert!(
+            include_str!("keyboard.rs") == generated,
+            "src/keyboard.rs is out of date with spec/"
+        );
+    }
+
+    #[test]
+    fn mouse_buttons() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(HostToWorker::MouseDown(mouse(0, 0., 0.)));
+        assert_eq!(
+            h.read::<MouseButtonInput>(),
+            vec![MouseButtonInput {
+                button: MouseButton::Left,
+                state: ButtonState::Pressed,
+                window,
+            }]
+        );
+        assert!(h.buttons().just_pressed(MouseButton::Left));
+
+        h.send(HostToWorker::MouseUp(mouse(0, 0., 0.)));
+        assert_eq!(
+            h.read::<MouseButtonInput>(),
+            vec![MouseButtonInput {
+                button: MouseButton::Left,
+                state: ButtonState::Released,
+                window,
+            }]
+        );
+        assert!(h.buttons().just_released(MouseButton::Left));
+        assert!(!h.buttons().pressed(MouseButton::Left));
+    }
+
+    #[test]
+    fn mouse_button_mapping() {
+        let mut h = Harness::new(1.0);
+        for (button, expected) in [
+            (1, MouseButton::Middle),
+            (2, MouseButton::Right),
+            (3, MouseButton::Back),
+            (4, MouseButton::Forward),
+            (7, MouseButton::Other(7)),
+        ] {
+            h.send(HostToWorker::MouseDown(mouse(button, 0., 0.)));
+            assert!(h.buttons().pressed(expected), "{button} -> {expected:?}");
+            assert_eq!(h.read::<MouseButtonInput>()[0].button, expected);
+        }
+    }
+
+    #[test]
+    fn cursor_moved() {
+        let mut h = Harness::new(2.0);
+        let window = h.window;
+
+        h.send(HostToWorker::MouseMove(mouse(0, 10., 20.)));
+        assert_eq!(
+            h.read::<CursorMoved>(),
+            vec![CursorMoved {
+                window,
+                position: Vec2::new(10., 20.),
+                delta: None,
+            }]
+        );
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.physical_cursor_position(), Some(Vec2::new(20., 40.)));
+    }
+
+    fn moved(x: f32, y: f32, movement_x: f32, movement_y: f32) -> HostToWorker {
+        HostToWorker::MouseMove(MouseEvent {
+            movement_x,
+            movement_y,
+            ..mouse(0, x, y)
+        })
+    }
+
+    #[test]
+    fn mouse_motion() {
+        let mut h = Harness::new(2.0);
+        // 逐个检查每次移动
+        h.world_mut()
+            .resource_mut::<coalesce::InputCoalescing>()
+            .enabled = false;
+
+        h.send(moved(10., 20., 0., 0.));
+        assert_eq!(h.read::<CursorMoved>()[0].delta, None);
+        assert!(h.read::<MouseMotion>().is_empty());
+
+        h.bridge.send(moved(13., 24., 3., 4.));
+        h.send(moved(15., 24., 2., 0.));
+        let deltas: Vec<_> = h.read::<CursorMoved>().iter().map(|c| c.delta).collect();
+        assert_eq!(
+            deltas,
+            vec![Some(Vec2::new(3., 4.)), Some(Vec2::new(2., 0.))]
+        );
+        // MouseMotion 为物理像素
+        assert_eq!(
+            h.read::<MouseMotion>(),
+            vec![
+                MouseMotion {
+                    delta: Vec2::new(6., 8.)
+                },
+                MouseMotion {
+                    delta: Vec2::new(4., 0.)
+                },
+            ]
+        );
+        let accumulated = h.world().resource::<AccumulatedMouseMotion>();
+        assert_eq!(accumulated.delta, Vec2::new(10., 8.));
+
+        h.frame();
+        let accumulated = h.world().resource::<AccumulatedMouseMotion>();
+        assert_eq!(accumulated.delta, Vec2::ZERO);
+    }
+
+    #[test]
+    fn mouse_motion_under_pointer_lock() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(moved(10., 20., 0., 0.));
+        h.read::<CursorMoved>();
+        h.world_mut()
+            .get_mut::<CursorOptions>(window)
+            .unwrap()
+            .grab_mode = CursorGrabMode::Locked;
+
+        // 锁定后 offsetX/Y 不再变化，只有 movementX/Y
+        h.send(moved(10., 20., -5., 7.));
+        assert!(h.read::<CursorMoved>().is_empty());
+        assert_eq!(
+            h.read::<MouseMotion>(),
+            vec![MouseMotion {
+                delta: Vec2::new(-5., 7.)
+            }]
+        );
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.cursor_position(), Some(Vec2::new(10., 20.)));
+    }
+
+    /// 光标坐标与 window 尺寸使用同一套逻辑坐标，与 DPR 无关
+    #[test]
+    fn coordinates_across_pixel_ratios() {
+        for scale_factor in [1., 1.5, 2., 3.] {
+            let mut h = Harness::new(scale_factor);
+            let window = h.window;
+            let w = h.world().get::<Window>(window).unwrap();
+            assert_eq!(w.scale_factor(), scale_factor);
+            let size = w.size();
+            assert_eq!(size * scale_factor, w.physical_size().as_vec2());
+
+            let position = size * 0.75;
+            h.send(HostToWorker::MouseMove(mouse(0, position.x, position.y)));
+            assert_eq!(h.read::<CursorMoved>()[0].position, position);
+            let w = h.world().get::<Window>(window).unwrap();
+            assert_eq!(w.cursor_position(), Some(position), "{scale_factor}");
+            assert_eq!(
+                w.physical_cursor_position(),
+                Some(w.physical_size().as_vec2() * 0.75),
+                "{scale_factor}"
+            );
+
+            // 缩放改变后逻辑尺寸不变，同一 CSS 位置仍对应同一逻辑坐标
+            h.send(HostToWorker::Resize(ResizeEvent {
+                width: size.x,
+                height: size.y,
+                device_pixel_ratio: scale_factor * 2.,
+            }));
+            h.send(HostToWorker::MouseMove(mouse(0, size.x / 2., size.y / 2.)));
+            let w = h.world().get::<Window>(window).unwrap();
+            assert_eq!(w.size(), size, "{scale_factor}");
+            assert_eq!(w.cursor_position(), Some(size / 2.), "{scale_factor}");
+            assert_eq!(h.read::<CursorMoved>()[0].position, size / 2.);
+        }
+    }
+
+    #[test]
+    fn keyboard_input() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::KeyDown(key("KeyA", "a")));
+        let input = h.read::<KeyboardInput>();
+        assert_eq!(input.len(), 1);
+        assert_eq!(input[0].key_code, KeyCode::KeyA);
+        assert_eq!(input[0].logical_key, Key::Character("a".into()));
+        assert_eq!(input[0].text, Some("a".into()));
+        assert_eq!(input[0].state, ButtonState::Pressed);
+        assert_eq!(input[0].window, h.window);
+        assert!(h.keys().just_pressed(KeyCode::KeyA));
+
+        h.send(HostToWorker::KeyUp(key("KeyA", "a")));
+        let input = h.read::<KeyboardInput>();
+        assert_eq!(input.len(), 1);
+        assert_eq!(input[0].state, ButtonState::Released);
+        assert!(h.keys().just_released(KeyCode::KeyA));
+        assert!(!h.keys().pressed(KeyCode::KeyA));
+    }
+
+    #[test]
+    fn logical_key_follows_layout() {
+        let mut h = Harness::new(1.0);
+
+        // AZERTY 布局下物理键 KeyQ 产生 "a"
+        h.send(HostToWorker::KeyDown(key("KeyQ", "a")));
+        h.send(HostToWorker::KeyDown(key("Digit1", "&")));
+        h.send(HostToWorker::KeyDown(key("KeyA", "A")));
+        h.send(HostToWorker::KeyDown(key("Space", " ")));
+        let input = h.read::<KeyboardInput>();
+        let keys: Vec<_> = input
+            .iter()
+            .map(|i| (i.key_code, i.logical_key.clone(), i.text.clone()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (KeyCode::KeyQ, Key::Character("a".into()), Some("a".into())),
+                (
+                    KeyCode::Digit1,
+                    Key::Character("&".into()),
+                    Some("&".into())
+                ),
+                (KeyCode::KeyA, Key::Character("A".into()), Some("A".into())),
+                (KeyCode::Space, Key::Character(" ".into()), Some(" ".into())),
+            ]
+        );
+    }
+
+    #[test]
+    fn named_and_dead_keys() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::KeyDown(key("Enter", "Enter")));
+        h.send(HostToWorker::KeyDown(key("Tab", "Tab")));
+        h.send(HostToWorker::KeyDown(key("ArrowLeft", "ArrowLeft")));
+        h.send(HostToWorker::KeyDown(key("Quote", "Dead")));
+        h.send(HostToWorker::KeyDown(key("ShiftLeft", "Shift")));
+        let input = h.read::<KeyboardInput>();
+        let keys: Vec<_> = input
+            .iter()
+            .map(|i| (i.key_code, i.logical_key.clone(), i.text.clone()))
+            .collect();
+        assert_eq!(
+            keys,
+            vec![
+                (KeyCode::Enter, Key::Enter, Some("\r".into())),
+                (KeyCode::Tab, Key::Tab, Some("\t".into())),
+                (KeyCode::ArrowLeft, Key::ArrowLeft, None),
+                (KeyCode::Quote, Key::Dead(None), None),
+                (KeyCode::ShiftLeft, Key::Shift, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_code_from_location() {
+        let mut h = Harness::new(1.0);
+        for (key_value, location, expected) in [
+            ("Shift", 1, KeyCode::ShiftLeft),
+            ("Shift", 2, KeyCode::ShiftRight),
+            ("Control", 2, KeyCode::ControlRight),
+            ("Meta", 1, KeyCode::SuperLeft),
+            ("7", 3, KeyCode::Numpad7),
+            ("Enter", 3, KeyCode::NumpadEnter),
+        ] {
+            h.send(HostToWorker::KeyDown(protocol::KeyboardEvent {
+                location,
+                ..key("", key_value)
+            }));
+            assert_eq!(h.read::<KeyboardInput>()[0].key_code, expected);
+        }
+
+        h.send(HostToWorker::KeyDown(key("", "7")));
+        assert_eq!(
+            h.read::<KeyboardInput>()[0].key_code,
+            KeyCode::Unidentified(NativeKeyCode::Unidentified)
+        );
+    }
+
+    #[test]
+    fn keyboard_repeat() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::KeyDown(key("Enter", "Enter")));
+        h.send(HostToWorker::KeyDown(protocol::KeyboardEvent {
+            repeat: true,
+            ..key("Enter", "Enter")
+        }));
+        let input = h.read::<KeyboardInput>();
+        assert_eq!(input.len(), 2);
+        assert!(!input[0].repeat);
+        assert!(input[1].repeat);
+        assert!(h.keys().pressed(KeyCode::Enter));
+        assert!(!h.keys().just_pressed(KeyCode::Enter));
+    }
+
+    #[test]
+    fn modifiers_follow_event_flags() {
+        let mut h = Harness::new(1.0);
+
+        // 在容器外按下的 Ctrl
+        h.send(HostToWorker::MouseDown(MouseEvent {
+            ctrl_key: true,
+            ..mouse(0, 0., 0.)
+        }));
+        assert!(h.keys().pressed(KeyCode::ControlLeft));
+        let input = h.read::<KeyboardInput>();
+        assert_eq!(input.len(), 1);
+        assert_eq!(input[0].logical_key, Key::Control);
+        assert_eq!(input[0].state, ButtonState::Pressed);
+
+        // 在容器外松开
+        h.send(HostToWorker::MouseMove(mouse(0, 1., 1.)));
+        assert!(!h.keys().pressed(KeyCode::ControlLeft));
+        assert_eq!(h.read::<KeyboardInput>()[0].state, ButtonState::Released);
+    }
+
+    #[test]
+    fn modifier_event_is_authoritative_for_its_own_key() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::KeyDown(key("ShiftRight", "Shift")));
+        h.send(HostToWorker::KeyDown(protocol::KeyboardEvent {
+            shift_key: true,
+            ..key("KeyA", "A")
+        }));
+        assert!(h.keys().pressed(KeyCode::ShiftRight));
+        assert!(!h.keys().pressed(KeyCode::ShiftLeft));
+        assert_eq!(h.read::<KeyboardInput>().len(), 2);
+
+        h.send(HostToWorker::KeyUp(key("KeyA", "a")));
+        assert!(!h.keys().pressed(KeyCode::ShiftRight));
+    }
+
+    #[test]
+    fn blur_releases_held_input() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(HostToWorker::KeyDown(key("KeyW", "w")));
+        h.send(HostToWorker::MouseDown(mouse(2, 0., 0.)));
+        // 与 blur 同一帧到达，尚未反映到 ButtonInput
+        h.bridge.send(HostToWorker::KeyDown(key("KeyD", "d")));
+        h.send(HostToWorker::Focus(false));
+
+        assert_eq!(h.keys().get_pressed().count(), 0);
+        assert_eq!(h.buttons().get_pressed().count(), 0);
+        let released: HashSet<_> = h
+            .read::<KeyboardInput>()
+            .into_iter()
+            .filter(|i| i.state == ButtonState::Released)
+            .map(|i| (i.key_code, i.logical_key))
+            .collect();
+        assert_eq!(
+            released,
+            HashSet::from_iter([
+                (KeyCode::KeyW, Key::Character("w".into())),
+                (KeyCode::KeyD, Key::Character("d".into())),
+            ])
+        );
+        assert_eq!(
+            h.read::<MouseButtonInput>().last(),
+            Some(&MouseButtonInput {
+                button: MouseButton::Right,
+                state: ButtonState::Released,
+                window,
+            })
+        );
+        assert_eq!(
+            h.read::<WindowFocused>(),
+            vec![WindowFocused {
+                window,
+                focused: false
+            }]
+        );
+        assert!(!h.world().get::<Window>(window).unwrap().focused);
+
+        h.send(HostToWorker::Focus(true));
+        assert!(h.read::<KeyboardInput>().is_empty());
+        assert!(h.read::<WindowFocused>()[0].focused);
+        assert!(h.world().get::<Window>(window).unwrap().focused);
+    }
+
+    #[test]
+    fn resize() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(HostToWorker::Resize(ResizeEvent {
+            width: 800.,
+            height: 450.5,
+            device_pixel_ratio: 2.,
+        }));
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.physical_size(), UVec2::new(1600, 901));
+        assert_eq!(w.scale_factor(), 2.);
+        assert_eq!((w.width(), w.height()), (800., 450.5));
+        assert_eq!(
+            h.read::<WindowScaleFactorChanged>(),
+            vec![WindowScaleFactorChanged {
+                window,
+                scale_factor: 2.
+            }]
+        );
+        assert_eq!(h.read::<WindowBackendScaleFactorChanged>().len(), 1);
+        assert_eq!(
+            h.read::<WindowResized>(),
+            vec![WindowResized {
+                window,
+                width: 800.,
+                height: 450.5
+            }]
+        );
+
+        // ResizeObserver 首次回调等重复的尺寸不产生消息
+        h.send(HostToWorker::Resize(ResizeEvent {
+            width: 800.,
+            height: 450.5,
+            device_pixel_ratio: 2.,
+        }));
+        assert!(h.read::<WindowResized>().is_empty());
+
+        h.send(HostToWorker::Resize(ResizeEvent {
+            width: 400.,
+            height: 300.,
+            device_pixel_ratio: 2.,
+        }));
+        assert!(h.read::<WindowScaleFactorChanged>().is_empty());
+        assert_eq!(h.read::<WindowResized>()[0].width, 400.);
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.physical_size(), UVec2::new(800, 600));
+    }
 }
@@ -0,0 +1,437 @@
+//! 以 [`CurrentVolume`] 为拾取体的射线求交，以及基于它的 bevy_picking 后端
+//!
+//! [`VolumeRayCast`] 以包围盒为粗检；启用 [`VolumePickingSettings::mesh_narrow_phase`] 后，
+//! 再与包围盒命中对象的 `Mesh3d` 三角形求交，得到表面上的交点、法线与三角形序号。
+//!
+//! 后端对 `RayMap` 中每条相机射线求交，结果以 `PointerHits` 交给 bevy_picking，
+//! 使 `Pointer<Over>`、`Pointer<Click>`、`Pointer<Drag>` 等观察者可以使用。
+//! 指针输入来自 worker 写入的 `WindowEvent`，见 [`write_window_event`](crate::write_window_event)
+
+use crate::bevy_app::CurrentVolume;
+use bevy::{
+    ecs::system::SystemParam,
+    math::bounding::{BoundingVolume, RayCast3d},
+    mesh::{Indices, PrimitiveTopology},
+    picking::{
+        Pickable, PickingSystems,
+        backend::{HitData, PointerHits, ray::RayMap},
+        mesh_picking::ray_cast::{Backfaces, RayMeshHit, ray_mesh_intersection},
+    },
+    prelude::*,
+};
+
+/// 射线的最远距离
+pub(crate) const MAX_DISTANCE: f32 = 30.;
+
+pub(crate) struct VolumePickingPlugin;
+
+impl Plugin for VolumePickingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VolumePickingSettings>()
+            .add_systems(PreUpdate, update_hits.in_set(PickingSystems::Backend));
+    }
+}
+
+#[derive(Resource, Debug, Default, Clone)]
+pub(crate) struct VolumePickingSettings {
+    /// 包围盒命中后再与 `Mesh3d` 的三角形求交。
+    /// 没有网格、网格未加载或不是三角形列表时仍以包围盒为准；
+    /// 网格超出包围盒的部分只在射线穿过包围盒时才能命中
+    pub mesh_narrow_phase: bool,
+}
+
+/// 射线与拾取体的交点
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VolumeHit {
+    pub entity: Entity,
+    /// 沿射线的距离
+    pub distance: f32,
+    pub point: Vec3,
+    /// 所在面的外法线
+    pub normal: Vec3,
+    /// 命中的三角形序号，只有三角形求交时才有
+    pub triangle_index: Option<usize>,
+}
+
+#[derive(SystemParam)]
+pub(crate) struct VolumeRayCast<'w, 's> {
+    settings: Res<'w, VolumePickingSettings>,
+    volumes: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static CurrentVolume,
+            Option<&'static Mesh3d>,
+            Option<&'static Transform>,
+        ),
+    >,
+    meshes: Option<Res<'w, Assets<Mesh>>>,
+}
+
+impl VolumeRayCast<'_, '_> {
+    /// `filter` 通过的对象中，所有与射线相交的，按距离由近到远排列
+    pub fn cast(&self, ray: Ray3d, filter: impl Fn(Entity) -> bool) -> Vec<VolumeHit> {
+        let mut hits: Vec<_> = self
+            .broad_phase(ray, filter)
+            .into_iter()
+            .filter_map(|(entity, volume, distance)| {
+                self.narrow_phase(ray, entity, volume, distance)
+            })
+            .collect();
+        hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        hits
+    }
+
+    /// 最近的交点
+    pub fn nearest(&self, ray: Ray3d, filter: impl Fn(Entity) -> bool) -> Option<VolumeHit> {
+        let mut nearest: Option<VolumeHit> = None;
+        for (entity, volume, distance) in self.broad_phase(ray, filter) {
+            // 包围盒按进入距离排序，之后的对象不可能更近；
+            // 三角形求交时网格可能超出包围盒，不能据此提前结束
+            if !self.settings.mesh_narrow_phase
+                && nearest.as_ref().is_some_and(|hit| hit.distance <= distance)
+            {
+                break;
+            }
+            if let Some(hit) = self.narrow_phase(ray, entity, volume, distance)
+                && nearest.as_ref().is_none_or(|n| hit.distance < n.distance)
+            {
+                nearest = Some(hit);
+            }
+        }
+        nearest
+    }
+
+    /// 包围盒求交，按进入距离由近到远排列
+    fn broad_phase(
+        &self,
+        ray: Ray3d,
+        filter: impl Fn(Entity) -> bool,
+    ) -> Vec<(Entity, &CurrentVolume, f32)> {
+        let ray_cast = RayCast3d::from_ray(ray, MAX_DISTANCE);
+        let mut hits: Vec<_> = self
+            .volumes
+            .iter()
+            .filter(|(entity, ..)| filter(*entity))
+            .filter_map(|(entity, volume, ..)| {
+                Some((entity, volume, ray_cast.aabb_intersection_at(volume)?))
+            })
+            .collect();
+        hits.sort_by(|a, b| a.2.total_cmp(&b.2));
+        hits
+    }
+
+    fn narrow_phase(
+        &self,
+        ray: Ray3d,
+        entity: Entity,
+        volume: &CurrentVolume,
+        distance: f32,
+    ) -> Option<VolumeHit> {
+        let aabb_hit = || {
+            let point = ray.get_point(distance);
+            VolumeHit {
+                entity,
+                distance,
+                point,
+                normal: face_normal(volume, point),
+                triangle_index: None,
+            }
+        };
+        if !self.settings.mesh_narrow_phase {
+            return Some(aabb_hit());
+        }
+        let Ok((_, _, Some(mesh), Some(transform))) = self.volumes.get(entity) else {
+            return Some(aabb_hit());
+        };
+        let Some(mesh) = self.meshes.as_ref().and_then(|meshes| meshes.get(mesh)) else {
+            return Some(aabb_hit());
+        };
+        let Some(hit) = ray_mesh(ray, mesh, transform) else {
+            return Some(aabb_hit());
+        };
+        let hit = hit?;
+        (hit.distance <= MAX_DISTANCE).then(|| VolumeHit {
+            entity,
+            distance: hit.distance,
+            point: hit.point,
+            normal: hit.normal.normalize_or_zero(),
+            triangle_index: hit.triangle_index,
+        })
+    }
+}
+
+/// 与网格的三角形求交；网格无法求交时为 `None`，求交但未命中时为 `Some(None)`
+fn ray_mesh(ray: Ray3d, mesh: &Mesh, transform: &Transform) -> Option<Option<RayMeshHit>> {
+    if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+        return None;
+    }
+    let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?.as_float3()?;
+    let normals = mesh
+        .attribute(Mesh::ATTRIBUTE_NORMAL)
+        .and_then(|normals| normals.as_float3());
+    // 与 `update_aabbes` 一致，以 `Transform` 为准
+    let affine = transform.compute_affine();
+    let cull = Backfaces::Cull;
+    Some(match mesh.indices() {
+        Some(Indices::U16(indices)) => {
+            ray_mesh_intersection(ray, &affine, positions, normals, Some(indices), None, cull)
+        }
+        Some(Indices::U32(indices)) => {
+            ray_mesh_intersection(ray, &affine, positions, normals, Some(indices), None, cull)
+        }
+        None => ray_mesh_intersection::<usize>(ray, &affine, positions, normals, None, None, cull),
+    })
+}
+
+fn update_hits(
+    ray_map: Res<RayMap>,
+    cameras: Query<&Camera>,
+    pickables: Query<&Pickable>,
+    ray_cast: VolumeRayCast,
+    mut hits: MessageWriter<PointerHits>,
+) {
+    let hoverable = |entity| pickables.get(entity).ok().is_none_or(|p| p.is_hoverable);
+    for (&ray_id, &ray) in ray_map.iter() {
+        let Ok(camera) = cameras.get(ray_id.camera) else {
+            continue;
+        };
+        let picks: Vec<_> = ray_cast
+            .cast(ray, hoverable)
+            .into_iter()
+            .map(|hit| {
+                let data = HitData::new(
+                    ray_id.camera,
+                    hit.distance,
+                    Some(hit.point),
+                    Some(hit.normal),
+                );
+                (hit.entity, data)
+            })
+            .collect();
+        if !picks.is_empty() {
+            hits.write(PointerHits::new(ray_id.pointer, picks, camera.order as f32));
+        }
+    }
+}
+
+/// 交点所在面的外法线：按半边长归一化后偏离中心最远的轴
+fn face_normal(volume: &CurrentVolume, position: Vec3) -> Vec3 {
+    let offset = (Vec3A::from(position) - volume.center())
+        / volume.half_size().max(Vec3A::splat(f32::EPSILON));
+    let abs = offset.abs();
+    let axis = if abs.x >= abs.y && abs.x >= abs.z {
+        Vec3::X
+    } else if abs.y >= abs.z {
+        Vec3::Y
+    } else {
+        Vec3::Z
+    };
+    axis * offset.dot(axis.into()).signum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::{HostToWorker, PointerEvent, PointerType},
+        test_harness::{Harness, pointer},
+    };
+    use bevy::{
+        ecs::system::RunSystemOnce,
+        math::bounding::{Aabb3d, Bounded3d},
+        picking::{
+            PickingPlugin, PickingSettings,
+            backend::ray::RayId,
+            input::PointerInputPlugin,
+            pointer::{PointerAction, PointerButton, PointerId, PointerInput},
+        },
+    };
+
+    fn harness() -> Harness {
+        Harness::with_plugins(1.0, |app| {
+            app.add_plugins((PointerInputPlugin, PickingPlugin, VolumePickingPlugin));
+        })
+    }
+
+    #[test]
+    fn worker_input_feeds_pointer_input() {
+        let mut h = harness();
+
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            ..pointer(PointerType::Mouse, 1, 30., 40.)
+        }));
+        h.send(HostToWorker::PointerDown(PointerEvent {
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, 30., 40.)
+        }));
+        h.send(HostToWorker::PointerDown(pointer(
+            PointerType::Touch,
+            2,
+            5.,
+            6.,
+        )));
+        let inputs = h.read::<PointerInput>();
+        let targets: Vec<_> = inputs
+            .iter()
+            .map(|input| (input.pointer_id, input.location.position))
+            .collect();
+        assert_eq!(
+            targets,
+            vec![
+                (PointerId::Mouse, Vec2::new(30., 40.)),
+                (PointerId::Mouse, Vec2::new(30., 40.)),
+                (PointerId::Mouse, Vec2::new(30., 40.)),
+                (PointerId::Touch(2), Vec2::new(5., 6.)),
+            ]
+        );
+        assert!(matches!(
+            inputs[0].action,
+            PointerAction::Move { delta } if delta == Vec2::new(30., 40.)
+        ));
+        // 按下前先移动到按下的位置
+        assert!(matches!(
+            inputs[1].action,
+            PointerAction::Move { delta } if delta == Vec2::ZERO
+        ));
+        assert!(matches!(
+            inputs[2].action,
+            PointerAction::Press(PointerButton::Primary)
+        ));
+        assert!(matches!(
+            inputs[3].action,
+            PointerAction::Press(PointerButton::Primary)
+        ));
+    }
+
+    #[test]
+    fn hits_current_volumes() {
+        let mut h = harness();
+        // 直接给出射线，不依赖相机的视口
+        h.world_mut()
+            .resource_mut::<PickingSettings>()
+            .is_input_enabled = false;
+        let camera = h.world_mut().spawn(Camera::default()).id();
+        let ray = Ray3d::new(Vec3::new(0., 0., 10.), Dir3::NEG_Z);
+        h.world_mut()
+            .resource_mut::<RayMap>()
+            .map
+            .insert(RayId::new(camera, PointerId::Mouse), ray);
+
+        let volume = |z: f32| CurrentVolume(Aabb3d::new(Vec3::new(0., 0., z), Vec3::splat(0.5)));
+        let near = h.world_mut().spawn(volume(2.)).id();
+        let far = h.world_mut().spawn(volume(-2.)).id();
+        h.world_mut().spawn((volume(0.), Pickable::IGNORE));
+        h.world_mut().spawn(volume(-100.));
+        h.world_mut().spawn(CurrentVolume(Aabb3d::new(
+            Vec3::new(3., 0., 0.),
+            Vec3::splat(0.5),
+        )));
+
+        h.frame();
+        let hits = h.read::<PointerHits>();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].pointer, PointerId::Mouse);
+        let mut picks = hits[0].picks.clone();
+        picks.sort_by(|a, b| a.1.depth.total_cmp(&b.1.depth));
+        assert_eq!(
+            picks,
+            vec![
+                (
+                    near,
+                    HitData::new(camera, 7.5, Some(Vec3::new(0., 0., 2.5)), Some(Vec3::Z))
+                ),
+                (
+                    far,
+                    HitData::new(camera, 11.5, Some(Vec3::new(0., 0., -1.5)), Some(Vec3::Z))
+                ),
+            ]
+        );
+    }
+
+    fn nearest(h: &mut Harness, ray: Ray3d) -> Option<VolumeHit> {
+        h.world_mut()
+            .run_system_once(move |ray_cast: VolumeRayCast| ray_cast.nearest(ray, |_| true))
+            .unwrap()
+    }
+
+    #[test]
+    fn nearest_of_overlapping_volumes() {
+        let mut h = harness();
+        let volume = |z: f32| CurrentVolume(Aabb3d::new(Vec3::new(0., 0., z), Vec3::splat(1.)));
+        h.world_mut().spawn(volume(-1.));
+        let near = h.world_mut().spawn(volume(0.)).id();
+
+        let hit = nearest(&mut h, Ray3d::new(Vec3::new(0., 0., 10.), Dir3::NEG_Z)).unwrap();
+        assert_eq!(hit.entity, near);
+        assert_eq!(hit.distance, 9.);
+        assert_eq!(hit.triangle_index, None);
+    }
+
+    #[test]
+    fn mesh_narrow_phase() {
+        let mut h = harness();
+        let mut meshes = Assets::<Mesh>::default();
+        let sphere = Sphere::new(1.);
+        let mesh = meshes.add(sphere.mesh().ico(3).unwrap());
+        h.world_mut().insert_resource(meshes);
+
+        let transform = Transform::from_xyz(0., 0., -2.);
+        let volume = sphere.aabb_3d(transform.translation);
+        let entity = h
+            .world_mut()
+            .spawn((Mesh3d(mesh), transform, CurrentVolume(volume)))
+            .id();
+        // 穿过包围盒的角落，但在球面之外
+        let corner = Ray3d::new(Vec3::new(0.9, 0.9, 10.), Dir3::NEG_Z);
+        let inside = Ray3d::new(Vec3::new(0.5, 0.5, 10.), Dir3::NEG_Z);
+
+        let hit = nearest(&mut h, corner).unwrap();
+        assert_eq!((hit.entity, hit.distance), (entity, 11.));
+
+        h.world_mut()
+            .resource_mut::<VolumePickingSettings>()
+            .mesh_narrow_phase = true;
+        assert_eq!(nearest(&mut h, corner), None);
+        let hit = nearest(&mut h, inside).unwrap();
+        assert_eq!(hit.entity, entity);
+        assert!(hit.triangle_index.is_some());
+        // 交点在球面上，比包围盒更远
+        assert!((hit.point.distance(transform.translation) - 1.).abs() < 0.02);
+        assert!(hit.distance > 11.);
+        let outward = (hit.point - transform.translation).normalize();
+        assert!(hit.normal.dot(outward) > 0.99);
+
+        // 网格不可用时退回包围盒
+        h.world_mut().entity_mut(entity).remove::<Mesh3d>();
+        let hit = nearest(&mut h, corner).unwrap();
+        assert_eq!(hit.triangle_index, None);
+    }
+
+    #[test]
+    fn mesh_outside_volume() {
+        let mut h = harness();
+        h.world_mut()
+            .resource_mut::<VolumePickingSettings>()
+            .mesh_narrow_phase = true;
+        let mut meshes = Assets::<Mesh>::default();
+        let small = meshes.add(Sphere::new(0.5).mesh().ico(3).unwrap());
+        let large = meshes.add(Sphere::new(1.).mesh().ico(3).unwrap());
+        h.world_mut().insert_resource(meshes);
+
+        let volume = |z: f32| CurrentVolume(Aabb3d::new(Vec3::new(0., 0., z), Vec3::splat(0.5)));
+        h.world_mut()
+            .spawn((Mesh3d(small), Transform::from_xyz(0., 0., 2.5), volume(2.5)));
+        // 包围盒比另一个对象更远，网格却更近
+        let entity = h
+            .world_mut()
+            .spawn((Mesh3d(large), Transform::from_xyz(0., 0., 5.), volume(-4.5)))
+            .id();
+
+        let hit = nearest(&mut h, Ray3d::new(Vec3::new(0., 0., 10.), Dir3::NEG_Z)).unwrap();
+        assert_eq!(hit.entity, entity);
+        assert!(hit.distance < 5.);
+    }
+}
@@ -0,0 +1,464 @@
+//! Pointer Events：触摸转为 `TouchInput`，鼠标与笔的主指针转为鼠标消息，笔的状态记录在 [`Pen`] 上
+
+use crate::{
+    WorkerApp,
+    protocol::{PointerEvent, PointerType},
+    write_window_event,
+};
+use bevy::{
+    prelude::*,
+    window::{CursorEntered, CursorLeft},
+};
+use bevy_input::{
+    ButtonState,
+    touch::{ForceTouch, TouchInput, TouchPhase},
+};
+
+/// 最近一次笔事件的状态
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct Pen {
+    pub pointer_id: i32,
+    /// 逻辑坐标
+    pub position: Vec2,
+    /// 笔尖或橡皮擦端接触画布
+    pub contact: bool,
+    /// 0 ~ 1
+    pub pressure: f32,
+    /// -1 ~ 1，如喷枪的滚轮
+    pub tangential_pressure: f32,
+    /// tiltX / tiltY，-90 ~ 90 度
+    pub tilt: Vec2,
+    /// 0 ~ 359 度
+    pub twist: f32,
+    /// 笔杆按钮
+    pub barrel_button: bool,
+    /// 正在使用橡皮擦端
+    pub eraser: bool,
+}
+
+impl WorkerApp {
+    /// 鼠标与笔的主指针驱动鼠标消息，与浏览器的兼容鼠标事件一致；
+    /// 触摸只产生 `TouchInput`，不模拟鼠标
+    pub(crate) fn on_pointer(&mut self, phase: TouchPhase, ev: PointerEvent) {
+        match ev.pointer_type {
+            PointerType::Touch => self.on_touch(phase, &ev),
+            PointerType::Pen => {
+                self.update_pen(phase, &ev);
+                if ev.is_primary {
+                    self.on_primary_pointer(phase, &ev);
+                }
+            }
+            PointerType::Mouse | PointerType::Unknown => {
+                if ev.is_primary {
+                    self.on_primary_pointer(phase, &ev);
+                }
+            }
+        }
+    }
+
+    /// 与 bevy_winit 一致：离开时清除 `Window` 上的光标位置。
+    /// 只有鼠标与笔的主指针对应窗口光标
+    pub(crate) fn on_pointer_crossing(&mut self, entered: bool, ev: PointerEvent) {
+        if !ev.is_primary || ev.pointer_type == PointerType::Touch {
+            return;
+        }
+
+        let window = self.window;
+        if entered {
+            if let Some(mut w) = self.world_mut().get_mut::<Window>(window) {
+                let position = Vec2::new(ev.offset_x, ev.offset_y) * w.scale_factor();
+                w.set_physical_cursor_position(Some(position.as_dvec2()));
+            }
+            write_window_event(self.world_mut(), CursorEntered { window });
+        } else {
+            if let Some(mut w) = self.world_mut().get_mut::<Window>(window) {
+                w.set_physical_cursor_position(None);
+            }
+            write_window_event(self.world_mut(), CursorLeft { window });
+        }
+    }
+
+    fn on_primary_pointer(&mut self, phase: TouchPhase, ev: &PointerEvent) {
+        match phase {
+            TouchPhase::Started => {
+                self.on_mouse_move(position(ev), movement(ev));
+                if let Some(button) = emulated_button(ev) {
+                    self.on_mouse_down(button);
+                }
+            }
+            TouchPhase::Moved => {
+                for sample in samples(ev) {
+                    self.on_mouse_move(position(sample), movement(sample));
+                }
+                // 已有按钮按下时，其它按钮的按下/松开只产生 pointermove
+                if let Some(button) = emulated_button(ev)
+                    && button >= 0
+                {
+                    if ev.buttons & button_mask(button) != 0 {
+                        self.on_mouse_down(button);
+                    } else {
+                        self.on_mouse_up(button);
+                    }
+                }
+            }
+            TouchPhase::Ended => {
+                if let Some(button) = emulated_button(ev) {
+                    self.on_mouse_up(button);
+                }
+            }
+            TouchPhase::Canceled => {
+                for button in std::mem::take(&mut self.held_buttons) {
+                    self.write_mouse_button(button, ButtonState::Released);
+                }
+            }
+        }
+    }
+
+    fn on_touch(&mut self, phase: TouchPhase, ev: &PointerEvent) {
+        let window = self.window;
+        let id = ev.pointer_id as u64;
+        for sample in samples(ev) {
+            write_window_event(
+                self.world_mut(),
+                TouchInput {
+                    phase,
+                    position: Vec2::new(sample.offset_x, sample.offset_y),
+                    window,
+                    force: Some(ForceTouch::Normalized(sample.pressure as f64)),
+                    id,
+                },
+            );
+        }
+    }
+
+    fn update_pen(&mut self, phase: TouchPhase, ev: &PointerEvent) {
+        let sample = samples(ev).last().unwrap_or(ev);
+        let lifted = matches!(phase, TouchPhase::Ended | TouchPhase::Canceled);
+        let mut pen = self.world_mut().resource_mut::<Pen>();
+        *pen = Pen {
+            pointer_id: ev.pointer_id,
+            position: Vec2::new(sample.offset_x, sample.offset_y),
+            contact: !lifted && ev.buttons & (PEN_CONTACT | PEN_ERASER) != 0,
+            pressure: sample.pressure,
+            tangential_pressure: sample.tangential_pressure,
+            tilt: Vec2::new(sample.tilt_x, sample.tilt_y),
+            twist: sample.twist,
+            barrel_button: ev.buttons & PEN_BARREL != 0,
+            eraser: ev.buttons & PEN_ERASER != 0,
+        };
+    }
+}
+
+/// https://w3c.github.io/pointerevents/#the-buttons-property
+const PEN_CONTACT: u16 = 1;
+const PEN_BARREL: u16 = 2;
+const PEN_ERASER: u16 = 32;
+/// 橡皮擦端的 `button` 与鼠标的第 6 个按钮同为 5
+const PEN_ERASER_BUTTON: i16 = 5;
+
+/// 转为鼠标按钮的 `button`。橡皮擦端不模拟鼠标按钮，以免与 `MouseButton::Other(5)` 混淆，
+/// 其状态只记录在 [`Pen::eraser`] 上
+fn emulated_button(ev: &PointerEvent) -> Option<i16> {
+    (ev.pointer_type != PointerType::Pen || ev.button != PEN_ERASER_BUTTON).then_some(ev.button)
+}
+
+/// 合并事件为空时（非 pointermove 或浏览器不支持）以事件自身为准
+fn samples(ev: &PointerEvent) -> &[PointerEvent] {
+    if ev.coalesced.is_empty() {
+        std::slice::from_ref(ev)
+    } else {
+        &ev.coalesced
+    }
+}
+
+fn position(ev: &PointerEvent) -> Vec2 {
+    Vec2::new(ev.offset_x, ev.offset_y)
+}
+
+fn movement(ev: &PointerEvent) -> Vec2 {
+    Vec2::new(ev.movement_x, ev.movement_y)
+}
+
+/// `button` 在 `buttons` 中对应的位，注意中键与右键的顺序相反
+fn button_mask(button: i16) -> u16 {
+    match button {
+        1 => 4,
+        2 => 2,
+        button => 1u16.checked_shl(button as u32).unwrap_or(0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coalesce::InputCoalescing,
+        protocol::HostToWorker,
+        test_harness::{Harness, pointer},
+    };
+    use bevy_input::{mouse::MouseButtonInput, touch::Touches};
+
+    #[test]
+    fn touches() {
+        let mut h = Harness::new(2.0);
+        h.world_mut().resource_mut::<InputCoalescing>().keep_samples = true;
+
+        h.send(HostToWorker::PointerDown(pointer(
+            PointerType::Touch,
+            7,
+            10.,
+            20.,
+        )));
+        h.send(HostToWorker::PointerDown(pointer(
+            PointerType::Touch,
+            8,
+            50.,
+            60.,
+        )));
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            coalesced: vec![
+                pointer(PointerType::Touch, 7, 11., 21.),
+                pointer(PointerType::Touch, 7, 12., 22.),
+            ],
+            ..pointer(PointerType::Touch, 7, 12., 22.)
+        }));
+        let input = h.read::<TouchInput>();
+        let phases: Vec<_> = input.iter().map(|t| (t.id, t.phase, t.position)).collect();
+        assert_eq!(
+            phases,
+            vec![
+                (7, TouchPhase::Started, Vec2::new(10., 20.)),
+                (8, TouchPhase::Started, Vec2::new(50., 60.)),
+                (7, TouchPhase::Moved, Vec2::new(11., 21.)),
+                (7, TouchPhase::Moved, Vec2::new(12., 22.)),
+            ]
+        );
+        assert_eq!(input[0].force, Some(ForceTouch::Normalized(0.5)));
+        let touches = h.world().resource::<Touches>();
+        assert_eq!(touches.iter().count(), 2);
+        assert_eq!(
+            touches.get_pressed(7).unwrap().position(),
+            Vec2::new(12., 22.)
+        );
+
+        h.send(HostToWorker::PointerUp(pointer(
+            PointerType::Touch,
+            7,
+            12.,
+            22.,
+        )));
+        h.send(HostToWorker::PointerCancel(pointer(
+            PointerType::Touch,
+            8,
+            50.,
+            60.,
+        )));
+        let touches = h.world().resource::<Touches>();
+        assert_eq!(touches.iter().count(), 0);
+        assert!(touches.just_canceled(8));
+
+        // 触摸不模拟鼠标
+        assert!(h.read::<MouseButtonInput>().is_empty());
+        assert!(h.read::<CursorMoved>().is_empty());
+    }
+
+    #[test]
+    fn primary_mouse_pointer() {
+        let mut h = Harness::new(1.0);
+        h.world_mut().resource_mut::<InputCoalescing>().keep_samples = true;
+
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            coalesced: vec![
+                pointer(PointerType::Mouse, 1, 1., 1.),
+                pointer(PointerType::Mouse, 1, 2., 2.),
+            ],
+            ..pointer(PointerType::Mouse, 1, 2., 2.)
+        }));
+        let moved: Vec<_> = h.read::<CursorMoved>().iter().map(|c| c.position).collect();
+        assert_eq!(moved, vec![Vec2::new(1., 1.), Vec2::new(2., 2.)]);
+
+        h.send(HostToWorker::PointerDown(PointerEvent {
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, 3., 3.)
+        }));
+        assert!(h.buttons().just_pressed(MouseButton::Left));
+        assert_eq!(h.read::<CursorMoved>()[0].position, Vec2::new(3., 3.));
+
+        // 按住左键时再按右键
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: 2,
+            buttons: 3,
+            ..pointer(PointerType::Mouse, 1, 3., 3.)
+        }));
+        assert!(h.buttons().just_pressed(MouseButton::Right));
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: 2,
+            buttons: 1,
+            ..pointer(PointerType::Mouse, 1, 3., 3.)
+        }));
+        assert!(h.buttons().just_released(MouseButton::Right));
+        assert!(h.buttons().pressed(MouseButton::Left));
+
+        h.send(HostToWorker::PointerUp(pointer(
+            PointerType::Mouse,
+            1,
+            3.,
+            3.,
+        )));
+        assert!(h.buttons().just_released(MouseButton::Left));
+
+        // 非主指针被忽略
+        h.read::<MouseButtonInput>();
+        h.send(HostToWorker::PointerDown(PointerEvent {
+            is_primary: false,
+            ..pointer(PointerType::Mouse, 2, 3., 3.)
+        }));
+        assert!(h.read::<MouseButtonInput>().is_empty());
+    }
+
+    #[test]
+    fn enter_and_leave() {
+        let mut h = Harness::new(2.0);
+        let window = h.window;
+
+        h.send(HostToWorker::PointerEnter(pointer(
+            PointerType::Mouse,
+            1,
+            0.,
+            5.,
+        )));
+        assert_eq!(h.read::<CursorEntered>(), vec![CursorEntered { window }]);
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.cursor_position(), Some(Vec2::new(0., 5.)));
+
+        h.send(HostToWorker::PointerMove(pointer(
+            PointerType::Mouse,
+            1,
+            7.,
+            5.,
+        )));
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.cursor_position(), Some(Vec2::new(7., 5.)));
+
+        h.send(HostToWorker::PointerLeave(pointer(
+            PointerType::Mouse,
+            1,
+            -1.,
+            5.,
+        )));
+        assert_eq!(h.read::<CursorLeft>(), vec![CursorLeft { window }]);
+        let w = h.world().get::<Window>(window).unwrap();
+        assert_eq!(w.cursor_position(), None);
+
+        // 触摸与非主指针不影响窗口光标
+        h.send(HostToWorker::PointerEnter(pointer(
+            PointerType::Touch,
+            2,
+            0.,
+            0.,
+        )));
+        h.send(HostToWorker::PointerEnter(PointerEvent {
+            is_primary: false,
+            ..pointer(PointerType::Mouse, 3, 0., 0.)
+        }));
+        assert!(h.read::<CursorEntered>().is_empty());
+    }
+
+    #[test]
+    fn pen() {
+        let mut h = Harness::new(1.0);
+
+        h.send(HostToWorker::PointerDown(PointerEvent {
+            buttons: 1,
+            pressure: 0.8,
+            tilt_x: 30.,
+            tilt_y: -15.,
+            twist: 90.,
+            ..pointer(PointerType::Pen, 3, 5., 6.)
+        }));
+        assert_eq!(
+            *h.world().resource::<Pen>(),
+            Pen {
+                pointer_id: 3,
+                position: Vec2::new(5., 6.),
+                contact: true,
+                pressure: 0.8,
+                tangential_pressure: 0.,
+                tilt: Vec2::new(30., -15.),
+                twist: 90.,
+                barrel_button: false,
+                eraser: false,
+            }
+        );
+        // 主笔指针同时作为鼠标
+        assert!(h.buttons().just_pressed(MouseButton::Left));
+
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            buttons: 1 | 32,
+            coalesced: vec![
+                PointerEvent {
+                    pressure: 0.6,
+                    ..pointer(PointerType::Pen, 3, 7., 8.)
+                },
+                PointerEvent {
+                    pressure: 0.4,
+                    ..pointer(PointerType::Pen, 3, 9., 10.)
+                },
+            ],
+            ..pointer(PointerType::Pen, 3, 9., 10.)
+        }));
+        let pen = h.world().resource::<Pen>();
+        assert_eq!(pen.position, Vec2::new(9., 10.));
+        assert_eq!(pen.pressure, 0.4);
+        assert!(pen.eraser);
+
+        h.send(HostToWorker::PointerUp(pointer(
+            PointerType::Pen,
+            3,
+            9.,
+            10.,
+        )));
+        assert!(!h.world().resource::<Pen>().contact);
+        assert!(h.buttons().just_released(MouseButton::Left));
+    }
+
+    #[test]
+    fn pen_eraser_contact() {
+        let mut h = Harness::new(1.0);
+
+        // 橡皮擦端接触时 buttons 只有 32
+        h.send(HostToWorker::PointerDown(PointerEvent {
+            button: 5,
+            buttons: 32,
+            pressure: 0.5,
+            ..pointer(PointerType::Pen, 4, 5., 6.)
+        }));
+        let pen = h.world().resource::<Pen>();
+        assert!(pen.contact && pen.eraser);
+        // 不模拟为鼠标按钮
+        assert_eq!(h.buttons().get_pressed().count(), 0);
+
+        // 悬停时不接触
+        h.send(HostToWorker::PointerUp(PointerEvent {
+            button: 5,
+            ..pointer(PointerType::Pen, 4, 5., 6.)
+        }));
+        assert_eq!(h.buttons().get_just_released().count(), 0);
+        h.send(HostToWorker::PointerMove(PointerEvent {
+            button: -1,
+            ..pointer(PointerType::Pen, 4, 7., 8.)
+        }));
+        let pen = h.world().resource::<Pen>();
+        assert!(!pen.contact && !pen.eraser);
+
+        // 鼠标的第 6 个按钮仍为 Other(5)
+        h.send(HostToWorker::PointerDown(PointerEvent {
+            button: 5,
+            buttons: 32,
+            ..pointer(PointerType::Mouse, 1, 7., 8.)
+        }));
+        assert!(h.buttons().just_pressed(MouseButton::Other(5)));
+    }
+}
@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+
+/// 主线程与 worker 之间的协议版本
+///
+/// "start" 消息携带主线程的版本号，与之不一致时 worker 拒绝启动；
+/// "ready" 消息回传 worker 的版本号，供主线程校验
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// 主线程 -> worker
+///
+/// 以 `ty` 字段区分消息类型，`event` 字段携带数据，新增消息只需在此追加变体。
+/// 事件以 structured clone 的普通对象传入，由 serde_wasm_bindgen 直接解出。
+///
+/// 使用相邻标签而非内部标签：内部标签须先把整个对象缓冲成 `Content`，
+/// 在 serde_wasm_bindgen 上要多出数倍的 js 调用
+#[derive(Debug, Deserialize)]
+#[serde(tag = "ty", content = "event", rename_all = "snake_case")]
+pub enum HostToWorker {
+    MouseUp(MouseEvent),
+    MouseDown(MouseEvent),
+    MouseMove(MouseEvent),
+    KeyUp(KeyboardEvent),
+    KeyDown(KeyboardEvent),
+    Wheel(WheelEvent),
+    PointerDown(PointerEvent),
+    PointerMove(PointerEvent),
+    PointerUp(PointerEvent),
+    /// 浏览器中止了指针（如触摸转为页面滚动）
+    PointerCancel(PointerEvent),
+    /// 指针进入画布
+    PointerEnter(PointerEvent),
+    /// 指针离开画布
+    PointerLeave(PointerEvent),
+    /// 画布的显示尺寸或 devicePixelRatio 改变
+    Resize(ResizeEvent),
+    /// 容器获得（`true`）或失去（`false`）焦点
+    Focus(bool),
+    /// 画布进入（`true`）或退出（`false`）指针锁定，回应 [`WorkerToHost::Cursor`]，
+    /// 请求被拒绝或用户按 Esc 退出时也会发送
+    PointerLock(bool),
+    /// 输入法开始组字，仅在 [`WorkerToHost::Ime`] 启用输入法后发送
+    CompositionStart(CompositionEvent),
+    /// 组字中的文本改变
+    CompositionUpdate(CompositionEvent),
+    /// 组字结束，`data` 为提交的文本（取消时为空）
+    CompositionEnd(CompositionEvent),
+    /// 回应 [`WorkerToHost::ClipboardWrite`] 或 [`WorkerToHost::ClipboardRead`]
+    Clipboard(ClipboardResult),
+    /// 轮询 `navigator.getGamepads()` 得到的全部已连接手柄，有变化时发送
+    Gamepads(Vec<GamepadSnapshot>),
+    /// 模拟 render 内的阻塞耗时（毫秒）
+    BlockRender(f64),
+}
+
+/// worker -> 主线程
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "ty", rename_all = "snake_case", rename_all_fields = "camelCase")]
+pub enum WorkerToHost {
+    /// app 初始化完成，可以开始接收事件
+    Ready { version: u32 },
+    /// 射线拾取结果
+    Pick { list: Vec<u64> },
+    /// 对最近一次在画布上按下的指针设置（`true`）或释放（`false`）pointer capture，
+    /// 使拖动到画布外时仍能收到移动与抬起事件
+    PointerCapture { capture: bool },
+    /// 主窗口的光标设置改变，宿主据此请求/退出指针锁定并设置 CSS cursor
+    Cursor {
+        grab_mode: CursorGrab,
+        visible: bool,
+        /// CSS cursor 关键字
+        icon: String,
+    },
+    /// 主窗口的 `ime_enabled` 或 `ime_position` 改变，
+    /// 宿主据此把接收文本输入的元素移到画布上的 (`x`, `y`)（CSS 像素）并聚焦
+    Ime { enabled: bool, x: f32, y: f32 },
+    /// 把文本写入剪贴板，宿主以相同的 `id` 回应 [`HostToWorker::Clipboard`]
+    ClipboardWrite { id: u32, text: String },
+    /// 读取剪贴板中的文本
+    ClipboardRead { id: u32 },
+    /// 一段时间内的输入延迟统计（毫秒）
+    InputStats {
+        /// 统计的输入事件数
+        events: usize,
+        /// 从宿主产生事件到 worker 在帧开始时取出
+        queue_delay: Percentiles,
+        /// 从宿主产生事件到该帧 update（含渲染提交）结束
+        latency: Percentiles,
+        /// 周期内收到的宿主消息数
+        received: u64,
+        /// 其中被输入合并掉的消息数
+        merged: u64,
+    },
+    /// 无法识别或解析失败的消息
+    Error { message: String },
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize)]
+pub struct Percentiles {
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+    pub max: f64,
+}
+
+/// 对应 bevy 的 `CursorGrabMode`，浏览器不支持 `Confined`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CursorGrab {
+    #[default]
+    None,
+    Confined,
+    Locked,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MouseEvent {
+    pub alt_key: bool,
+    pub button: i16,
+    pub buttons: u16,
+    pub client_x: f32,
+    pub client_y: f32,
+    pub ctrl_key: bool,
+    pub meta_key: bool,
+    /// 与上一次事件的位移（CSS 像素），指针锁定时仍然有效
+    #[serde(default)]
+    pub movement_x: f32,
+    #[serde(default)]
+    pub movement_y: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub page_x: f64,
+    pub page_y: f64,
+    pub screen_x: f64,
+    pub screen_y: f64,
+    pub shift_key: bool,
+    /// 宿主的 `performance.timeOrigin + event.timeStamp`（毫秒），用于统计输入延迟
+    #[serde(default)]
+    pub time_stamp: f64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyboardEvent {
+    pub alt_key: bool,
+    pub code: String,
+    pub ctrl_key: bool,
+    pub is_composing: bool,
+    pub key: String,
+    pub location: u32,
+    pub meta_key: bool,
+    pub shift_key: bool,
+    pub repeat: bool,
+    /// 同 [`MouseEvent::time_stamp`]
+    #[serde(default)]
+    pub time_stamp: f64,
+}
+
+/// [CompositionEvent](https://developer.mozilla.org/en-US/docs/Web/API/CompositionEvent)
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompositionEvent {
+    pub data: String,
+    /// 同 [`MouseEvent::time_stamp`]
+    #[serde(default)]
+    pub time_stamp: f64,
+}
+
+/// 剪贴板请求的结果，`error` 为空时成功，读取的结果在 `text` 中
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardResult {
+    pub id: u32,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+/// [PointerEvent](https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent)
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PointerEvent {
+    pub pointer_id: i32,
+    pub pointer_type: PointerType,
+    pub is_primary: bool,
+    pub alt_key: bool,
+    pub button: i16,
+    pub buttons: u16,
+    pub ctrl_key: bool,
+    pub meta_key: bool,
+    /// 与上一次事件的位移（CSS 像素），指针锁定时仍然有效
+    #[serde(default)]
+    pub movement_x: f32,
+    #[serde(default)]
+    pub movement_y: f32,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub shift_key: bool,
+    pub pressure: f32,
+    pub tangential_pressure: f32,
+    pub tilt_x: f32,
+    pub tilt_y: f32,
+    pub twist: f32,
+    pub width: f32,
+    pub height: f32,
+    /// 同 [`MouseEvent::time_stamp`]
+    #[serde(default)]
+    pub time_stamp: f64,
+    /// pointermove 的 getCoalescedEvents()，按时间先后排列
+    #[serde(default)]
+    pub coalesced: Vec<PointerEvent>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PointerType {
+    #[default]
+    Mouse,
+    Pen,
+    Touch,
+    #[serde(other)]
+    Unknown,
+}
+
+/// [Gamepad](https://developer.mozilla.org/en-US/docs/Web/API/Gamepad)
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamepadSnapshot {
+    /// 在 `getGamepads()` 中的位置，断开前保持不变
+    pub index: u32,
+    pub id: String,
+    /// `"standard"` 表示按钮与轴按标准布局排列
+    pub mapping: String,
+    /// 各按钮的 value，0 ~ 1
+    pub buttons: Vec<f32>,
+    /// -1 ~ 1，y 轴向下为正
+    pub axes: Vec<f32>,
+}
+
+/// 画布的逻辑尺寸（CSS 像素）与 devicePixelRatio
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResizeEvent {
+    pub width: f32,
+    pub height: f32,
+    pub device_pixel_ratio: f32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WheelEvent {
+    pub delta_x: f32,
+    pub delta_y: f32,
+    pub delta_z: f32,
+    pub delta_mode: u8,
+    /// 同 [`MouseEvent::time_stamp`]
+    #[serde(default)]
+    pub time_stamp: f64,
+}
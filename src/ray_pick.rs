@@ -1,13 +1,17 @@
-use crate::bevy_app::{CurrentVolume, Hovered, InDrag, Selected, Target};
-use bevy::{
-    ecs::message::MessageReader, input::mouse::MouseWheel, math::bounding::RayCast3d,
-    platform::collections::HashMap, prelude::*,
+use crate::bevy_app::{Hovered, InDrag, Selected, Target};
+use crate::bridge::Host;
+use crate::click::{Click, ClickSystems};
+use crate::gesture::LongPressGesture;
+use crate::picking::{VolumePickingSettings, VolumeRayCast};
+use crate::protocol::WorkerToHost;
+use crate::wheel::WheelSettings;
+use bevy::{ecs::message::MessageReader, prelude::*, window::PrimaryWindow};
+use bevy_input::{
+    common_conditions::*,
+    gestures::{PanGesture, PinchGesture},
+    touch::Touches,
 };
-use bevy_input::common_conditions::*;
-use js_sys::global;
 use std::ops::Range;
-use wasm_bindgen::JsCast;
-use web_sys::DedicatedWorkerGlobalScope;
 
 pub(crate) struct RayPickPlugin;
 
@@ -19,78 +23,85 @@ struct CursorPosition {
 impl Plugin for RayPickPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(CameraSettings {
-            orthographic_viewport_height: 5.,
             // In orthographic projections, we specify camera scale relative to a default value of 1,
             // in which one unit in world space corresponds to one pixel.
             orthographic_zoom_range: 0.1..3000.0,
-            // This value was hand-tuned to ensure that zooming in and out feels smooth but not slow.
-            orthographic_zoom_speed: 0.2,
+            perspective_fov_range: 0.1..3.0,
+            // 滚一格（3 行）缩放约 15%
+            wheel_zoom_sensitivity: 0.05,
         });
-        app.init_resource::<CursorPosition>().add_systems(
-            Update,
-            (
+        app.init_resource::<CursorPosition>()
+            .init_resource::<VolumePickingSettings>()
+            .add_systems(
+                Update,
                 (
-                    position_system.run_if(on_message::<CursorMoved>),
-                    hover_system.run_if(on_message::<CursorMoved>),
-                    drag_start_system.run_if(input_just_pressed(MouseButton::Left)),
-                    drag_finish_system.run_if(input_just_released(MouseButton::Left)),
-                    drag_system.run_if(on_message::<CursorMoved>),
-                    select_system.run_if(input_just_pressed(MouseButton::Left)),
-                )
-                    .chain(),
-                zoom_system,
-            ),
-        );
+                    (
+                        position_system.run_if(on_message::<CursorMoved>),
+                        hover_system
+                            .run_if(on_message::<CursorMoved>)
+                            .before(ClickSystems),
+                        leave_system.run_if(on_message::<CursorLeft>),
+                        drag_start_system.run_if(input_just_pressed(MouseButton::Left)),
+                        drag_finish_system.run_if(input_just_released(MouseButton::Left)),
+                        long_press_system.run_if(on_message::<LongPressGesture>),
+                        touch_drag_finish_system.run_if(touch_just_released),
+                        drag_system.run_if(on_message::<CursorMoved>.or(on_message::<PanGesture>)),
+                        select_system
+                            .run_if(on_message::<Click>)
+                            .after(ClickSystems),
+                    )
+                        .chain(),
+                    zoom_system,
+                ),
+            );
     }
 }
 
 use bevy::input::mouse::AccumulatedMouseScroll;
 
 #[derive(Debug, Resource)]
-struct CameraSettings {
-    /// The height of the viewport in world units when the orthographic camera's scale is 1
-    pub orthographic_viewport_height: f32,
+pub(crate) struct CameraSettings {
     /// Clamp the orthographic camera's scale to this range
     pub orthographic_zoom_range: Range<f32>,
-    /// Multiply mouse wheel inputs by this factor when using the orthographic camera
-    pub orthographic_zoom_speed: f32,
+    /// 透视相机的 fov（弧度）范围
+    pub perspective_fov_range: Range<f32>,
+    /// 每滚动一行的缩放比例，像素滚动按 [`WheelSettings::line_height`] 换算为行
+    pub wheel_zoom_sensitivity: f32,
 }
 
 fn zoom_system(
     camera: Single<&mut Projection, With<Camera>>,
+    window: Single<&Window, With<PrimaryWindow>>,
     camera_settings: Res<CameraSettings>,
+    wheel_settings: Res<WheelSettings>,
     mouse_wheel_input: Res<AccumulatedMouseScroll>,
+    mut pinch: MessageReader<PinchGesture>,
 ) {
+    // 双指张开为正，与滚轮向上一样放大：可见范围缩小为 1 / (1 + pinch)
+    let pinch_zoom = pinch.read().map(|p| 1. + p.0).product::<f32>();
+    let lines = wheel_settings.lines(&mouse_wheel_input, window.scale_factor());
+    // We want scrolling up to zoom in, decreasing the scale, so we negate the delta.
+    let delta_zoom = -lines.y * camera_settings.wheel_zoom_sensitivity;
+    // When changing scales, logarithmic changes are more intuitive.
+    // To get this effect, we add 1 to the delta, so that a delta of 0
+    // results in no multiplicative effect, positive values result in a multiplicative increase,
+    // and negative values result in multiplicative decreases.
+    let multiplicative_zoom = (1. + delta_zoom).max(0.) / pinch_zoom;
+    if multiplicative_zoom == 1. {
+        return;
+    }
+
     match *camera.into_inner() {
         Projection::Orthographic(ref mut orthographic) => {
-            /*
-            info!(
-                "Mouse wheel delta: {:?}",
-                mouse_wheel_input.delta.y
-            );
-             */
-            // We want scrolling up to zoom in, decreasing the scale, so we negate the delta.
-            let delta_zoom = -mouse_wheel_input.delta.y * camera_settings.orthographic_zoom_speed;
-            // When changing scales, logarithmic changes are more intuitive.
-            // To get this effect, we add 1 to the delta, so that a delta of 0
-            // results in no multiplicative effect, positive values result in a multiplicative increase,
-            // and negative values result in multiplicative decreases.
-            let multiplicative_zoom = 1. + delta_zoom;
-
-            // info!("Orthographic scale: {}", orthographic.scale);
             orthographic.scale = (orthographic.scale * multiplicative_zoom).clamp(
                 camera_settings.orthographic_zoom_range.start,
                 camera_settings.orthographic_zoom_range.end,
             );
         }
         Projection::Perspective(ref mut perspective) => {
-            // We want scrolling up to zoom in, decreasing the scale, so we negate the delta.
-            let delta_zoom = -mouse_wheel_input.delta.y * camera_settings.orthographic_zoom_speed;
-
-            // Adjust the field of view, but keep it within our stated range.
-            perspective.fov = (perspective.fov + delta_zoom).clamp(
-                camera_settings.orthographic_zoom_range.start,
-                camera_settings.orthographic_zoom_range.end,
+            perspective.fov = (perspective.fov * multiplicative_zoom).clamp(
+                camera_settings.perspective_fov_range.start,
+                camera_settings.perspective_fov_range.end,
             );
         }
         _ => info!("not orthographic camera, skip zoom"),
@@ -110,9 +121,15 @@ fn zoom_system(
 
 fn drag_start_system(
     mut commands: Commands,
+    host: Res<Host>,
     p: Res<CursorPosition>,
     hovered: Query<Entity, (With<Hovered>, Without<InDrag>)>,
 ) {
+    if hovered.is_empty() {
+        return;
+    }
+    // 拖到画布外松开时也要能结束拖动
+    host.post(WorkerToHost::PointerCapture { capture: true });
     for entity in hovered.iter() {
         commands.entity(entity).insert(InDrag {
             position: p.position,
@@ -122,26 +139,97 @@ fn drag_start_system(
 
 fn drag_finish_system(
     mut commands: Commands,
+    host: Res<Host>,
     in_drag: Query<(Entity, &mut Transform), With<InDrag>>,
 ) {
+    if in_drag.is_empty() {
+        return;
+    }
+    host.post(WorkerToHost::PointerCapture { capture: false });
     for (entity, _) in in_drag.iter() {
         commands.entity(entity).remove::<InDrag>();
     }
 }
 
+/// 长按选中手指下最近的对象并开始拖动
+fn long_press_system(
+    mut commands: Commands,
+    mut long_press: MessageReader<LongPressGesture>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    old: Query<Entity, With<Selected>>,
+    targets: Query<(), With<Target>>,
+    ray_cast: VolumeRayCast,
+) {
+    let Ok((camera, transform)) = cameras.single() else {
+        return;
+    };
+    for ev in long_press.read() {
+        let Some(ray) = ray_from_screenspace(ev.position, camera, transform) else {
+            continue;
+        };
+        let Some(hit) = ray_cast.nearest(ray, |entity| targets.contains(entity)) else {
+            continue;
+        };
+
+        for entity in old.iter() {
+            commands.entity(entity).remove::<Selected>();
+        }
+        commands.entity(hit.entity).insert((
+            Selected {},
+            InDrag {
+                position: ev.position,
+            },
+        ));
+    }
+}
+
+fn touch_just_released(touches: Res<Touches>) -> bool {
+    touches.any_just_released() || touches.any_just_canceled()
+}
+
+/// 所有手指抬起后结束触摸拖动
+fn touch_drag_finish_system(
+    mut commands: Commands,
+    touches: Res<Touches>,
+    in_drag: Query<Entity, With<InDrag>>,
+) {
+    if touches.iter().next().is_some() {
+        return;
+    }
+    for entity in in_drag.iter() {
+        commands.entity(entity).remove::<InDrag>();
+    }
+}
+
+/// 鼠标移动或平移手势拖动对象；平移手势下没有拖动的对象时平移相机
 fn drag_system(
     mut cursor_moved: MessageReader<CursorMoved>,
-    cameras: Query<(&Camera, &GlobalTransform)>,
-    mut in_drag: Query<(Entity, &mut Transform, &mut InDrag), With<InDrag>>,
+    mut pan: MessageReader<PanGesture>,
+    camera: Single<(&Camera, &GlobalTransform, &mut Transform)>,
+    mut in_drag: Query<(&mut Transform, &mut InDrag), Without<Camera>>,
 ) {
-    if let Some(last) = cursor_moved.read().last() {
-        let (camera, global_transform) = cameras.single().unwrap();
-        let cur = screen_to_world(last.position, camera, global_transform).unwrap();
-        for (entity, mut transform, mut in_drag) in in_drag.iter_mut() {
-            let start = screen_to_world(in_drag.position, camera, global_transform).unwrap();
-            let offset = cur - start;
-            transform.translation += offset;
-            in_drag.position = last.position;
+    let (camera, global_transform, mut camera_transform) = camera.into_inner();
+    let cursor = cursor_moved.read().last().map(|c| c.position);
+    // 平移手势为物理像素，换算为逻辑坐标
+    let pan = pan.read().map(|p| p.0).sum::<Vec2>() / camera.target_scaling_factor().unwrap_or(1.);
+
+    for (mut transform, mut in_drag) in in_drag.iter_mut() {
+        let position = cursor.unwrap_or(in_drag.position) + pan;
+        let start = screen_to_world(in_drag.position, camera, global_transform);
+        let cur = screen_to_world(position, camera, global_transform);
+        if let (Some(start), Some(cur)) = (start, cur) {
+            transform.translation += cur - start;
+        }
+        in_drag.position = position;
+    }
+
+    if in_drag.is_empty() && pan != Vec2::ZERO {
+        // 以视口中心为参照，让场景跟随手指移动
+        let center = camera.logical_viewport_size().unwrap_or_default() / 2.;
+        let start = screen_to_world(center, camera, global_transform);
+        let cur = screen_to_world(center + pan, camera, global_transform);
+        if let (Some(start), Some(cur)) = (start, cur) {
+            camera_transform.translation += start - cur;
         }
     }
 }
@@ -152,72 +240,86 @@ fn position_system(mut cursor_moved: MessageReader<CursorMoved>, mut p: ResMut<C
     }
 }
 
+/// 只有光标下最近的对象悬停
 fn hover_system(
     mut commands: Commands,
+    host: Res<Host>,
     mut cursor_moved: MessageReader<CursorMoved>,
     cameras: Query<(&Camera, &GlobalTransform)>,
-    q: Query<(Entity, &CurrentVolume), With<Target>>,
+    hovered: Query<Entity, With<Hovered>>,
+    targets: Query<(), With<Target>>,
+    ray_cast: VolumeRayCast,
 ) {
-    let mut hovers: HashMap<Entity, u64> = HashMap::default();
-    for ev in cursor_moved.read() {
-        let (camera, transform) = cameras.single().unwrap();
-        let ray = ray_from_screenspace(ev.position, camera, transform).unwrap();
-        let ray_cast = RayCast3d::from_ray(ray, 30.);
-
-        // 计算射线拾取
-        for (entity, volume) in q.iter() {
-            // 射线求交
+    let Some(ev) = cursor_moved.read().last() else {
+        return;
+    };
+    let Ok((camera, transform)) = cameras.single() else {
+        return;
+    };
+    // 光标在视口外时没有射线，视为未命中
+    let hit = ray_from_screenspace(ev.position, camera, transform)
+        .and_then(|ray| ray_cast.nearest(ray, |entity| targets.contains(entity)));
+    if let Some(hit) = &hit {
+        info!(
+            "toi: {}, entity: {:?}, triangle: {:?}",
+            hit.distance, hit.entity, hit.triangle_index
+        );
+    }
+
+    for entity in hovered.iter() {
+        if Some(entity) != hit.as_ref().map(|hit| hit.entity) {
             commands.entity(entity).remove::<Hovered>();
-            let toi = ray_cast.aabb_intersection_at(volume);
-            if toi.is_some() {
-                info!("toi: {:?}, entity: {:?}", toi, entity);
-                commands.entity(entity).insert(Hovered {});
-                hovers.insert(entity, entity.to_bits());
-            }
         }
     }
+    if let Some(hit) = &hit {
+        commands.entity(hit.entity).insert(Hovered {});
+    }
 
     // 通知 js pick 结果
-    if let Ok(global) = global().dyn_into::<DedicatedWorkerGlobalScope>() {
-        let picks: Vec<u64> = hovers.values().copied().collect();
-        info!("[worker] -> hover: {:?}", &picks);
-        let msg = super::web_ffi::Message {
-            ty: "pick".to_string(),
-            list: Some(picks),
-            ..default()
-        };
+    let picks: Vec<u64> = hit.iter().map(|hit| hit.entity.to_bits()).collect();
+    info!("[worker] -> hover: {:?}", &picks);
+    host.post(WorkerToHost::Pick { list: picks });
+}
 
-        let val = serde_wasm_bindgen::to_value(&msg).unwrap();
-        global.post_message(&val).unwrap();
+/// 光标离开画布后清除悬停
+fn leave_system(mut commands: Commands, host: Res<Host>, hovered: Query<Entity, With<Hovered>>) {
+    for entity in hovered.iter() {
+        commands.entity(entity).remove::<Hovered>();
     }
+    host.post(WorkerToHost::Pick { list: Vec::new() });
 }
 
+/// 左键单击选中按下时悬停的对象，单击空白处取消选中；拖动不改变选中
 fn select_system(
     mut commands: Commands,
+    mut clicks: MessageReader<Click>,
     old: Query<Entity, With<Selected>>,
-    hovered: Query<Entity, With<Hovered>>,
 ) {
-    info!("[worker] left button pressed, re-select ...",);
+    let Some(click) = clicks
+        .read()
+        .filter(|click| click.button == MouseButton::Left)
+        .last()
+    else {
+        return;
+    };
+    info!("[worker] left click, re-select {:?}", click.entity);
     for entity in old.iter() {
         commands.entity(entity).remove::<Selected>();
     }
-    for entity in hovered.iter() {
+    if let Some(entity) = click.entity {
         commands.entity(entity).insert(Selected {});
     }
 }
 
-/// 构造一条相机射线
+/// 构造一条相机射线，`cursor_pos_screen` 为窗口逻辑坐标
 fn ray_from_screenspace(
     cursor_pos_screen: Vec2,
     camera: &Camera,
     camera_transform: &GlobalTransform,
 ) -> Option<Ray3d> {
-    let mut viewport_pos = cursor_pos_screen;
-    if let Some(viewport) = &camera.viewport {
-        viewport_pos -= viewport.physical_position.as_vec2();
-    }
+    // viewport_to_world 自行减去换算为逻辑坐标的视口位置
     camera
-        .viewport_to_world(camera_transform, viewport_pos)
+        .viewport_to_world(camera_transform, cursor_pos_screen)
         .ok()
 }
 
@@ -236,3 +338,121 @@ fn screen_to_world(
     }
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        click::ClickPlugin,
+        gesture::GesturePlugin,
+        protocol::{HostToWorker, PointerType},
+        test_harness::{Harness, mouse, pointer, wheel},
+    };
+
+    #[test]
+    fn leaving_clears_hover() {
+        let mut h = Harness::with_plugins(1.0, |app| {
+            app.add_plugins((GesturePlugin, ClickPlugin, RayPickPlugin));
+        });
+        let hovered = h.world_mut().spawn((Target {}, Hovered {})).id();
+
+        h.send(HostToWorker::PointerLeave(pointer(
+            PointerType::Mouse,
+            1,
+            -1.,
+            0.,
+        )));
+        assert!(h.world().get::<Hovered>(hovered).is_none());
+        assert_eq!(
+            h.bridge.take_posted(),
+            vec![WorkerToHost::Pick { list: Vec::new() }]
+        );
+    }
+
+    #[test]
+    fn hover_without_camera() {
+        let mut h = Harness::with_plugins(1.0, |app| {
+            app.add_plugins((GesturePlugin, ClickPlugin, RayPickPlugin));
+        });
+
+        h.send(HostToWorker::MouseMove(mouse(0, 10., 0.)));
+        h.world_mut().spawn(Camera::default());
+        h.world_mut().spawn(Camera::default());
+        h.send(HostToWorker::MouseMove(mouse(0, 20., 0.)));
+        assert!(h.bridge.take_posted().is_empty());
+    }
+
+    #[test]
+    fn ray_through_offset_viewport() {
+        let mut camera = Camera {
+            viewport: Some(bevy::camera::Viewport {
+                physical_position: UVec2::new(200, 100),
+                physical_size: UVec2::new(400, 300),
+                ..default()
+            }),
+            ..default()
+        };
+        camera.computed.target_info = Some(bevy::camera::RenderTargetInfo {
+            physical_size: UVec2::new(800, 600),
+            scale_factor: 2.,
+        });
+        camera.computed.clip_from_view = Mat4::perspective_infinite_reverse_rh(1., 4. / 3., 0.1);
+
+        // 视口中心的逻辑坐标：(200 + 400 / 2) / 2, (100 + 300 / 2) / 2
+        let ray = ray_from_screenspace(Vec2::new(200., 125.), &camera, &GlobalTransform::IDENTITY)
+            .unwrap();
+        assert!(ray.direction.distance(Vec3::NEG_Z) < 1e-5);
+    }
+
+    #[test]
+    fn select_on_click_only() {
+        let mut h = Harness::with_plugins(1.0, |app| {
+            app.add_plugins((GesturePlugin, ClickPlugin, RayPickPlugin));
+        });
+        let hovered = h.world_mut().spawn(Hovered {}).id();
+        h.send(HostToWorker::MouseMove(mouse(0, 10., 0.)));
+
+        // 按下时还不确定是单击还是拖动
+        h.send(HostToWorker::MouseDown(mouse(0, 10., 0.)));
+        assert!(h.world().get::<Selected>(hovered).is_none());
+        h.send(HostToWorker::MouseUp(mouse(0, 10., 0.)));
+        assert!(h.world().get::<Selected>(hovered).is_some());
+
+        // 在空白处拖动不取消选中
+        h.world_mut().entity_mut(hovered).remove::<Hovered>();
+        h.send(HostToWorker::MouseDown(mouse(0, 10., 0.)));
+        h.send(HostToWorker::MouseMove(mouse(0, 100., 0.)));
+        h.send(HostToWorker::MouseUp(mouse(0, 100., 0.)));
+        assert!(h.world().get::<Selected>(hovered).is_some());
+
+        h.send(HostToWorker::MouseDown(mouse(0, 100., 0.)));
+        h.send(HostToWorker::MouseUp(mouse(0, 100., 0.)));
+        assert!(h.world().get::<Selected>(hovered).is_none());
+    }
+
+    #[test]
+    fn wheel_zoom() {
+        let mut h = Harness::with_plugins(2.0, |app| {
+            app.add_plugins((GesturePlugin, ClickPlugin, RayPickPlugin));
+        });
+        h.world_mut()
+            .spawn((Camera::default(), Projection::default()));
+        let fov = |h: &mut Harness| {
+            let mut q = h.world_mut().query::<&Projection>();
+            match q.single(h.world()).unwrap() {
+                Projection::Perspective(p) => p.fov,
+                _ => unreachable!(),
+            }
+        };
+        let start = fov(&mut h);
+
+        // 向上滚一格放大，按行与按像素的效果一致
+        h.send(HostToWorker::Wheel(wheel(0., -3., 1)));
+        let lines = fov(&mut h);
+        assert!((lines - start * 0.85).abs() < 1e-5);
+
+        h.send(HostToWorker::Wheel(wheel(0., -100., 0)));
+        let pixels = fov(&mut h);
+        assert!((pixels - lines * 0.85).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,147 @@
+//! 原生环境下的无渲染测试工具：以合成的宿主消息驱动 `WorkerApp`
+
+use crate::{
+    WorkerApp,
+    bridge::MemoryBridge,
+    protocol::{HostToWorker, KeyboardEvent, MouseEvent, PointerEvent, PointerType, WheelEvent},
+};
+use bevy::{
+    ecs::message::{Message, MessageCursor, Messages},
+    input::InputPlugin,
+    platform::collections::HashMap,
+    prelude::*,
+    window::{WindowPlugin, WindowResolution},
+};
+use std::{
+    any::{Any, TypeId},
+    ops::{Deref, DerefMut},
+};
+
+pub(crate) struct Harness {
+    pub app: WorkerApp,
+    pub bridge: MemoryBridge,
+    cursors: HashMap<TypeId, Box<dyn Any>>,
+}
+
+impl Deref for Harness {
+    type Target = WorkerApp;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl DerefMut for Harness {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.app
+    }
+}
+
+impl Harness {
+    pub fn new(scale_factor: f32) -> Self {
+        Self::with_plugins(scale_factor, |_| {})
+    }
+
+    /// 在最小插件集之上追加被测插件，并跑完首帧（窗口就绪）
+    ///
+    /// 与 `spawn_window` 一样，窗口的缩放系数即 devicePixelRatio
+    pub fn with_plugins(scale_factor: f32, plugins: impl FnOnce(&mut App)) -> Self {
+        let mut resolution = WindowResolution::new(1200, 675);
+        resolution.set_scale_factor(scale_factor);
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            InputPlugin,
+            WindowPlugin {
+                primary_window: Some(Window {
+                    resolution,
+                    ..default()
+                }),
+                ..default()
+            },
+        ));
+        plugins(&mut app);
+
+        let bridge = MemoryBridge::default();
+        let mut harness = Self {
+            app: WorkerApp::new(app, bridge.clone()),
+            bridge,
+            cursors: HashMap::default(),
+        };
+        harness.app.frame();
+        assert_ne!(harness.app.window, Entity::PLACEHOLDER);
+        harness.bridge.take_posted();
+        harness
+    }
+
+    /// 发送一条宿主消息并执行一帧
+    pub fn send(&mut self, msg: HostToWorker) -> &mut Self {
+        self.bridge.send(msg);
+        self.app.frame();
+        self
+    }
+
+    /// 自上次读取以来写入的全部 `M` 消息
+    pub fn read<M: Message + Clone>(&mut self) -> Vec<M> {
+        let cursor = self
+            .cursors
+            .entry(TypeId::of::<M>())
+            .or_insert_with(|| Box::new(MessageCursor::<M>::default()))
+            .downcast_mut::<MessageCursor<M>>()
+            .unwrap();
+        let messages = self.app.world().resource::<Messages<M>>();
+        cursor.read(messages).cloned().collect()
+    }
+
+    pub fn buttons(&self) -> &ButtonInput<MouseButton> {
+        self.app.world().resource()
+    }
+
+    pub fn keys(&self) -> &ButtonInput<KeyCode> {
+        self.app.world().resource()
+    }
+}
+
+pub(crate) fn mouse(button: i16, offset_x: f32, offset_y: f32) -> MouseEvent {
+    MouseEvent {
+        button,
+        offset_x,
+        offset_y,
+        ..default()
+    }
+}
+
+/// 主指针，按下时的默认压力为 0.5
+pub(crate) fn pointer(
+    pointer_type: PointerType,
+    pointer_id: i32,
+    offset_x: f32,
+    offset_y: f32,
+) -> PointerEvent {
+    PointerEvent {
+        pointer_id,
+        pointer_type,
+        is_primary: true,
+        offset_x,
+        offset_y,
+        pressure: 0.5,
+        ..default()
+    }
+}
+
+pub(crate) fn key(code: &str, key: &str) -> KeyboardEvent {
+    KeyboardEvent {
+        code: code.to_string(),
+        key: key.to_string(),
+        ..default()
+    }
+}
+
+pub(crate) fn wheel(delta_x: f32, delta_y: f32, delta_mode: u8) -> WheelEvent {
+    WheelEvent {
+        delta_x,
+        delta_y,
+        delta_mode,
+        ..default()
+    }
+}
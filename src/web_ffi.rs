@@ -1,9 +1,16 @@
-use crate::{WorkerApp, bevy_app::init_app};
+use crate::{
+    WorkerApp,
+    bevy_app::init_app,
+    bridge::{Host, HostBridge},
+    protocol::{HostToWorker, PROTOCOL_VERSION, WorkerToHost},
+};
 use bevy::prelude::*;
 use js_sys::global;
 use once_cell::sync::OnceCell;
-use serde::{Deserialize, Serialize};
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
 use wasm_bindgen::prelude::*;
 use web_sys::DedicatedWorkerGlobalScope;
 
@@ -23,176 +30,120 @@ extern "C" {
     ///
     /// 在 worker 环境执行
     pub(crate) fn block_from_worker();
-}
 
-fn set_onmessage() {
-    let global = global().dyn_into::<DedicatedWorkerGlobalScope>().unwrap();
-    let closure: Closure<dyn Fn(web_sys::MessageEvent)> = Closure::new(on_message);
-    let func = closure.as_ref().unchecked_ref::<js_sys::Function>().clone();
-    global.set_onmessage(Some(&func));
-    closure.forget();
+    /// 设置 block_from_worker 的阻塞时长（毫秒）
+    pub(crate) fn set_render_block_time(ms: f64);
 }
 
-fn post_message(val: &JsValue) {
-    let global = global().dyn_into::<DedicatedWorkerGlobalScope>().unwrap();
-    let _ = global.post_message(val);
+/// 浏览器 worker 环境下的宿主通信桥
+///
+/// onmessage 中解析出的消息先放入队列，由 `WorkerApp::frame` 在每帧开始时取出
+pub(crate) struct WorkerBridge {
+    inbox: Arc<Mutex<VecDeque<HostToWorker>>>,
 }
 
-#[wasm_bindgen]
-pub async fn init_bevy_app(canvas: web_sys::OffscreenCanvas, scale_factor: f32) {
-    let app = init_app(canvas, scale_factor);
-    let _ = APP.set(Box::into_raw(Box::new(app)) as u64);
-
-    // take over event loop
-    set_onmessage();
-
-    let f = Rc::new(RefCell::new(None));
-    let g = f.clone();
-    *g.borrow_mut() = Some(Closure::new({
-        let app = wapp();
-        move || {
-            if app.window == Entity::PLACEHOLDER {
-                if app.try_finish() {
-                    let val = serde_wasm_bindgen::to_value(&Message {
-                        ty: "ready".to_string(),
-                        ..default()
-                    })
-                    .unwrap();
-                    post_message(&val);
+impl WorkerBridge {
+    /// 接管 worker 的 onmessage
+    pub(crate) fn new() -> Self {
+        let inbox: Arc<Mutex<VecDeque<HostToWorker>>> = Arc::default();
+        let queue = inbox.clone();
+        let closure: Closure<dyn Fn(web_sys::MessageEvent)> =
+            Closure::new(move |ev: web_sys::MessageEvent| {
+                match serde_wasm_bindgen::from_value::<HostToWorker>(ev.data()) {
+                    Ok(HostToWorker::BlockRender(ms)) => set_render_block_time(ms),
+                    Ok(msg) => queue.lock().unwrap().push_back(msg),
+                    Err(e) => send_to_host(&WorkerToHost::Error {
+                        message: e.to_string(),
+                    }),
                 }
-                request_animation_frame(f.borrow().as_ref().unwrap());
-                return;
-            }
-
-            block_from_worker();
-            app.update();
-            if app.should_exit().is_some() {
-                app.close_window();
-                return;
-            }
-
-            request_animation_frame(f.borrow().as_ref().unwrap());
-        }
-    }));
-    request_animation_frame(g.borrow().as_ref().unwrap());
-}
+            });
 
-// https://wasm-bindgen.github.io/wasm-bindgen/examples/request-animation-frame.html
-// https://developer.mozilla.org/en-US/docs/Web/API/DedicatedWorkerGlobalScope/requestAnimationFrame
-fn request_animation_frame(f: &Closure<dyn FnMut()>) {
-    let _ = global()
-        .dyn_into::<DedicatedWorkerGlobalScope>()
-        .unwrap()
-        .request_animation_frame(f.as_ref().unchecked_ref());
-}
+        let global = global().dyn_into::<DedicatedWorkerGlobalScope>().unwrap();
+        let func = closure.as_ref().unchecked_ref::<js_sys::Function>().clone();
+        global.set_onmessage(Some(&func));
+        closure.forget();
 
-#[derive(Debug, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct MouseEvent {
-    alt_key: bool,
-    button: i16,
-    buttons: u16,
-    client_x: f32,
-    client_y: f32,
-    ctrl_key: bool,
-    meta_key: bool,
-    offset_x: f32,
-    offset_y: f32,
-    page_x: f64,
-    page_y: f64,
-    screen_x: f64,
-    screen_y: f64,
-    shift_key: bool,
+        Self { inbox }
+    }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct KeyboardEvent {
-    alt_key: bool,
-    code: String,
-    ctrl_key: bool,
-    is_composing: bool,
-    key: String,
-    location: u32,
-    meta_key: bool,
-    shift_key: bool,
-    repeat: bool,
-}
+impl HostBridge for WorkerBridge {
+    fn post(&self, msg: WorkerToHost) {
+        send_to_host(&msg);
+    }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct WheelEvent {
-    delta_x: f32,
-    delta_y: f32,
-    delta_z: f32,
-    delta_mode: u8,
+    fn recv(&self) -> Option<HostToWorker> {
+        self.inbox.lock().unwrap().pop_front()
+    }
+
+    // https://wasm-bindgen.github.io/wasm-bindgen/examples/request-animation-frame.html
+    // https://developer.mozilla.org/en-US/docs/Web/API/DedicatedWorkerGlobalScope/requestAnimationFrame
+    fn schedule_frame(&self) {
+        FRAME.with(|f| {
+            let f = f.get_or_init(|| Closure::new(on_frame));
+            let _ = global()
+                .dyn_into::<DedicatedWorkerGlobalScope>()
+                .unwrap()
+                .request_animation_frame(f.as_ref().unchecked_ref());
+        });
+    }
+
+    // 与主线程的 timeOrigin 不同，加上各自的 timeOrigin 后才能比较
+    fn now(&self) -> f64 {
+        let performance = global()
+            .dyn_into::<DedicatedWorkerGlobalScope>()
+            .unwrap()
+            .performance()
+            .unwrap();
+        performance.time_origin() + performance.now()
+    }
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
-pub struct Message {
-    pub ty: String,
-    pub event: Option<String>,
-    pub list: Option<Vec<u64>>,
+thread_local! {
+    static FRAME: std::cell::OnceCell<Closure<dyn FnMut()>> = const { std::cell::OnceCell::new() };
 }
 
-fn on_message(ev: web_sys::MessageEvent) {
-    let data = ev.data();
-    let msg: Message = serde_wasm_bindgen::from_value(data).unwrap_or_default();
-    match msg.ty.as_str() {
-        "mouse_up" | "mouse_down" | "mouse_move" => {
-            if let Some(ev) = msg.event {
-                on_mouse_event(msg.ty.as_str(), ev);
-            }
-        }
-        "key_up" | "key_down" => {
-            if let Some(ev) = msg.event {
-                on_key_event(msg.ty.as_str(), ev);
-            }
-        }
-        "wheel" => {
-            if let Some(ev) = msg.event {
-                on_wheel_event(msg.ty.as_str(), ev);
-            }
-        }
-        _ => {}
+fn post_message(val: &JsValue) {
+    if let Ok(global) = global().dyn_into::<DedicatedWorkerGlobalScope>() {
+        let _ = global.post_message(val);
     }
-
-    /*
-    case "blockRender":
-      renderBlockTime = data.blockTime;
-     */
 }
 
-fn on_mouse_event(r#type: &str, event: String) {
-    let d = serde_json::from_str::<MouseEvent>(&event).unwrap_or_default();
-    info!("[worker] <- {}: {:?}", r#type, &d);
-    let app = wapp();
-    match r#type {
-        "mouse_up" => app.on_mouse_up(d.button),
-        "mouse_down" => app.on_mouse_down(d.button, d.offset_x, d.offset_y),
-        "mouse_move" => app.on_mouse_move(d.offset_x, d.offset_y),
-        _ => {}
+fn send_to_host(msg: &WorkerToHost) {
+    match serde_wasm_bindgen::to_value(msg) {
+        Ok(val) => post_message(&val),
+        Err(e) => log(&format!("[worker] failed to encode {:?}: {}", msg, e)),
     }
 }
 
-fn on_key_event(r#type: &str, event: String) {
-    let d = serde_json::from_str::<KeyboardEvent>(&event).unwrap_or_default();
-    info!("[worker] <- {}: {:?}", r#type, &d);
-    let app = wapp();
-    match r#type {
-        "key_up" => app.on_key_up(d.code, d.repeat),
-        "key_down" => app.on_key_down(d.code, d.repeat),
-        _ => {}
+#[wasm_bindgen]
+pub async fn init_bevy_app(canvas: web_sys::OffscreenCanvas, scale_factor: f32, version: u32) {
+    if version != PROTOCOL_VERSION {
+        send_to_host(&WorkerToHost::Error {
+            message: format!(
+                "protocol version mismatch: host {}, worker {}",
+                version, PROTOCOL_VERSION
+            ),
+        });
+        return;
     }
+
+    // take over event loop
+    let bridge = WorkerBridge::new();
+    let app = init_app(canvas, scale_factor, bridge);
+    let _ = APP.set(Box::into_raw(Box::new(app)) as u64);
+    wapp().world().resource::<Host>().schedule_frame();
 }
 
-fn on_wheel_event(r#type: &str, event: String) {
-    let d = serde_json::from_str::<WheelEvent>(&event).unwrap_or_default();
-    info!("[worker] <- {}: {:?}", r#type, &d);
-    wapp().on_wheel(d.delta_x, d.delta_y, d.delta_mode);
+fn on_frame() {
+    let app = wapp();
+    if app.window != Entity::PLACEHOLDER {
+        block_from_worker();
+    }
+    app.frame();
 }
 
 fn wapp() -> &'static mut WorkerApp {
     let ptr = APP.get().copied().unwrap();
     unsafe { &mut *(ptr as *mut WorkerApp) }
-}
\ No newline at end of file
+}
@@ -0,0 +1,195 @@
+//! 滚轮：DOM 的 `WheelEvent` 转为 bevy 的 `MouseWheel`
+//!
+//! 与 winit 一致，方向取反（向上/向左滚为正），像素为物理像素。
+//! 按页滚动（`DOM_DELTA_PAGE`）按画布尺寸换算为像素，`deltaZ` 作为 [`MouseWheelZ`]
+
+use crate::{WorkerApp, protocol::WheelEvent, write_window_event};
+use bevy::prelude::*;
+use bevy_input::mouse::{AccumulatedMouseScroll, MouseScrollUnit, MouseWheel};
+
+/// https://w3c.github.io/uievents/#dom-wheelevent-dom_delta_line
+const DOM_DELTA_LINE: u8 = 1;
+const DOM_DELTA_PAGE: u8 = 2;
+
+#[derive(Resource, Debug, Clone)]
+pub struct WheelSettings {
+    /// 一行对应的 CSS 像素
+    pub line_height: f32,
+    /// 把按行滚动也换算为像素，使各浏览器的滚轮数值一致
+    /// （Firefox 按行，Chrome 与 Safari 按像素）
+    pub normalize: bool,
+}
+
+impl Default for WheelSettings {
+    fn default() -> Self {
+        Self {
+            // Chrome 滚一格为 100 像素、3 行
+            line_height: 100. / 3.,
+            normalize: false,
+        }
+    }
+}
+
+impl WheelSettings {
+    /// 把累计的滚动量换算为行数，供缩放等不区分单位的场景使用
+    pub fn lines(&self, scroll: &AccumulatedMouseScroll, scale_factor: f32) -> Vec2 {
+        match scroll.unit {
+            MouseScrollUnit::Line => scroll.delta,
+            MouseScrollUnit::Pixel => scroll.delta / (self.line_height * scale_factor),
+        }
+    }
+}
+
+/// 滚轮的 z 轴（`deltaZ`），bevy 的 `MouseWheel` 没有对应字段
+#[derive(Message, Debug, Clone, Copy, PartialEq)]
+pub struct MouseWheelZ {
+    pub unit: MouseScrollUnit,
+    pub z: f32,
+    pub window: Entity,
+}
+
+impl WorkerApp {
+    pub(crate) fn on_wheel(&mut self, ev: WheelEvent) {
+        let window = self.window;
+        let world = self.world_mut();
+        let Some(w) = world.get::<Window>(window) else {
+            return;
+        };
+        let (size, scale_factor) = (w.size(), w.scale_factor());
+        let settings = world.resource::<WheelSettings>();
+
+        let delta = -Vec3::new(ev.delta_x, ev.delta_y, ev.delta_z);
+        let (delta, unit) = match ev.delta_mode {
+            DOM_DELTA_LINE if !settings.normalize => (delta, MouseScrollUnit::Line),
+            DOM_DELTA_LINE => (
+                delta * settings.line_height * scale_factor,
+                MouseScrollUnit::Pixel,
+            ),
+            // z 轴没有对应的画布尺寸，按高度换算
+            DOM_DELTA_PAGE => (
+                delta * size.extend(size.y) * scale_factor,
+                MouseScrollUnit::Pixel,
+            ),
+            _ => (delta * scale_factor, MouseScrollUnit::Pixel),
+        };
+
+        if delta.truncate() != Vec2::ZERO {
+            write_window_event(
+                world,
+                MouseWheel {
+                    unit,
+                    x: delta.x,
+                    y: delta.y,
+                    window,
+                },
+            );
+        }
+        if delta.z != 0. {
+            world.write_message(MouseWheelZ {
+                unit,
+                z: delta.z,
+                window,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        protocol::HostToWorker,
+        test_harness::{Harness, wheel},
+    };
+
+    #[test]
+    fn mouse_wheel() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(HostToWorker::Wheel(wheel(0., 3., 1)));
+        assert_eq!(
+            h.read::<MouseWheel>(),
+            vec![MouseWheel {
+                unit: MouseScrollUnit::Line,
+                x: 0.,
+                y: -3.,
+                window,
+            }]
+        );
+        let scroll = h.world().resource::<AccumulatedMouseScroll>();
+        assert_eq!(scroll.unit, MouseScrollUnit::Line);
+        assert_eq!(scroll.delta, Vec2::new(0., -3.));
+
+        h.send(HostToWorker::Wheel(wheel(5., -100., 0)));
+        assert_eq!(h.read::<MouseWheel>()[0].unit, MouseScrollUnit::Pixel);
+        let scroll = h.world().resource::<AccumulatedMouseScroll>();
+        assert_eq!(scroll.delta, Vec2::new(-5., 100.));
+
+        h.frame();
+        let scroll = h.world().resource::<AccumulatedMouseScroll>();
+        assert_eq!(scroll.delta, Vec2::ZERO);
+    }
+
+    #[test]
+    fn pixels_are_physical() {
+        let mut h = Harness::new(2.0);
+
+        h.bridge.send(HostToWorker::Wheel(wheel(0., 10., 0)));
+        h.send(HostToWorker::Wheel(wheel(4., 0., 0)));
+        let scroll = h.world().resource::<AccumulatedMouseScroll>();
+        assert_eq!(scroll.unit, MouseScrollUnit::Pixel);
+        assert_eq!(scroll.delta, Vec2::new(-8., -20.));
+    }
+
+    #[test]
+    fn page_mode_uses_canvas_size() {
+        let mut h = Harness::new(2.0);
+        let size = h.world().get::<Window>(h.window).unwrap().size();
+
+        h.send(HostToWorker::Wheel(wheel(0.5, 1., 2)));
+        let wheel = h.read::<MouseWheel>();
+        assert_eq!(wheel[0].unit, MouseScrollUnit::Pixel);
+        assert_eq!(
+            Vec2::new(wheel[0].x, wheel[0].y),
+            Vec2::new(-size.x, -size.y * 2.)
+        );
+    }
+
+    #[test]
+    fn delta_z() {
+        let mut h = Harness::new(1.0);
+        let window = h.window;
+
+        h.send(HostToWorker::Wheel(WheelEvent {
+            delta_z: 2.,
+            delta_mode: 1,
+            ..default()
+        }));
+        assert!(h.read::<MouseWheel>().is_empty());
+        assert_eq!(
+            h.read::<MouseWheelZ>(),
+            vec![MouseWheelZ {
+                unit: MouseScrollUnit::Line,
+                z: -2.,
+                window,
+            }]
+        );
+    }
+
+    #[test]
+    fn normalize_lines() {
+        let mut h = Harness::new(2.0);
+        h.world_mut().resource_mut::<WheelSettings>().normalize = true;
+
+        // Firefox 滚一格与 Chrome 一致
+        h.send(HostToWorker::Wheel(wheel(0., 3., 1)));
+        let scroll = h.world().resource::<AccumulatedMouseScroll>();
+        assert_eq!(scroll.unit, MouseScrollUnit::Pixel);
+        assert!((scroll.delta.y + 200.).abs() < 1e-3);
+
+        let settings = h.world().resource::<WheelSettings>();
+        let lines = settings.lines(scroll, 2.);
+        assert!((lines.y + 3.).abs() < 1e-5);
+    }
+}